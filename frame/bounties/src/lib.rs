@@ -0,0 +1,1127 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Bounties Pallet
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod weights;
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	dispatch::DispatchInfo,
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement::AllowDeath, IsSubType, ReservableCurrency},
+	BoundedVec, PalletId,
+};
+use frame_system::pallet_prelude::*;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{AccountIdConversion, Bounded, Dispatchable, TransactionExtension, Zero},
+	transaction_validity::{InvalidTransaction, TransactionValidityError, ValidTransaction},
+	Permill,
+};
+pub use weights::WeightInfo;
+
+pub use pallet::*;
+
+/// An index of a bounty. Just a `u32`.
+pub type BountyIndex = u32;
+
+/// An index of a milestone within a bounty's milestone list.
+pub type MilestoneIndex = u32;
+
+type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// The status of a bounty proposal.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo, Debug)]
+pub enum BountyStatus<AccountId, BlockNumber> {
+	/// The bounty is proposed and waiting for approval.
+	Proposed,
+	/// The bounty is approved and waiting to become active.
+	Approved,
+	/// The bounty is funded and waiting for curator assignment.
+	Funded,
+	/// A curator has been proposed and is waiting to accept.
+	CuratorProposed {
+		/// The assigned curator of this bounty.
+		curator: AccountId,
+	},
+	/// The bounty is active and waiting to be awarded.
+	Active {
+		/// The assigned curator of this bounty.
+		curator: AccountId,
+		/// The update due block, after which the bounty can be closed without the curator's
+		/// consent.
+		update_due: BlockNumber,
+	},
+	/// The bounty is awarded and waiting to released after a delay.
+	PendingPayout {
+		/// The assigned curator of this bounty.
+		curator: AccountId,
+		/// The beneficiary of the bounty.
+		beneficiary: AccountId,
+		/// When the bounty can be claimed.
+		unlock_at: BlockNumber,
+	},
+}
+
+/// A bounty proposal.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo, Debug)]
+pub struct Bounty<AccountId, Balance, BlockNumber> {
+	/// The account proposing it.
+	pub proposer: AccountId,
+	/// The (total) amount that should be paid if the bounty is rewarded.
+	pub value: Balance,
+	/// The curator fee, deducted from `value` when paid.
+	pub fee: Balance,
+	/// The deposit of funds the curator has lodged.
+	pub curator_deposit: Balance,
+	/// The deposit the proposer lodged for creating this bounty.
+	pub bond: Balance,
+	/// The status of this bounty.
+	pub status: BountyStatus<AccountId, BlockNumber>,
+	/// The number of blocks of curator inactivity that are tolerated before the curator may be
+	/// slashed via [`Pallet::slash_inactive_curator`].
+	pub activity_period: BlockNumber,
+}
+
+/// A single milestone of a milestone-based bounty payout.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo, Debug)]
+pub struct Milestone<AccountId, Balance, BlockNumber> {
+	/// The amount paid out when this milestone is claimed.
+	pub amount: Balance,
+	/// The account paid out to.
+	pub beneficiary: AccountId,
+	/// The block at which this milestone becomes claimable, once awarded.
+	pub unlock_block: BlockNumber,
+	/// Whether this milestone has already been claimed.
+	pub claimed: bool,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The currency in which bounties are paid out.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The amount held on deposit for placing a bounty proposal.
+		#[pallet::constant]
+		type BountyDepositBase: Get<BalanceOf<Self>>;
+
+		/// The amount held on deposit per byte of bounty description.
+		#[pallet::constant]
+		type DataDepositPerByte: Get<BalanceOf<Self>>;
+
+		/// Minimum value for a bounty.
+		#[pallet::constant]
+		type BountyValueMinimum: Get<BalanceOf<Self>>;
+
+		/// Maximum acceptable reason length.
+		#[pallet::constant]
+		type MaximumReasonLength: Get<u32>;
+
+		/// The maximum number of approved bounty payouts processed by a single call to
+		/// [`Pallet::spend_funds`].
+		#[pallet::constant]
+		type MaxApprovalsPerSpend: Get<u32>;
+
+		/// The default number of blocks of curator inactivity tolerated before
+		/// [`Pallet::slash_inactive_curator`] becomes callable.
+		#[pallet::constant]
+		type DefaultActivityPeriod: Get<BlockNumberFor<Self>>;
+
+		/// The pallet's account, used to hold bounty funds in escrow between funding and payout.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Origin allowed to propose a curator for a funded bounty.
+		type ApproveOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The curator deposit is calculated as a percentage of the curator fee.
+		///
+		/// This deposit has optional upper and lower bounds with `CuratorDepositMax` and
+		/// `CuratorDepositMin`.
+		#[pallet::constant]
+		type CuratorDepositMultiplier: Get<Permill>;
+
+		/// Maximum amount of funds that should be placed in a deposit for a curator.
+		#[pallet::constant]
+		type CuratorDepositMax: Get<Option<BalanceOf<Self>>>;
+
+		/// Minimum amount of funds that should be placed in a deposit for a curator.
+		#[pallet::constant]
+		type CuratorDepositMin: Get<Option<BalanceOf<Self>>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::storage]
+	pub type BountyCount<T> = StorageValue<_, BountyIndex, ValueQuery>;
+
+	#[pallet::storage]
+	pub type Bounties<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BountyIndex,
+		Bounty<T::AccountId, BalanceOf<T>, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	pub type BountyDescriptions<T: Config> =
+		StorageMap<_, Twox64Concat, BountyIndex, BoundedVec<u8, T::MaximumReasonLength>, OptionQuery>;
+
+	/// Bounties that have been approved and are waiting to be paid out by [`Pallet::spend_funds`].
+	///
+	/// Stored as a plain `Vec` rather than a `BoundedVec`: the backlog can grow arbitrarily large,
+	/// which is exactly why `spend_funds` drains it through a resumable
+	/// [`BountyApprovalsCursor`] instead of processing it all in one block.
+	#[pallet::storage]
+	pub type BountyApprovals<T> = StorageValue<_, alloc::vec::Vec<BountyIndex>, ValueQuery>;
+
+	/// The index into [`BountyApprovals`] that the next call to [`Pallet::spend_funds`] should
+	/// resume from. `None` means start from the front.
+	#[pallet::storage]
+	pub type BountyApprovalsCursor<T> = StorageValue<_, u32, OptionQuery>;
+
+	#[pallet::storage]
+	pub type BountyMilestones<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		BountyIndex,
+		BoundedVec<Milestone<T::AccountId, BalanceOf<T>, BlockNumberFor<T>>, ConstU32<10>>,
+		ValueQuery,
+	>;
+
+	/// Per-contributor totals added to a bounty's value via [`Pallet::add_bounty_funds`], kept so
+	/// that contributions can be refunded pro-rata if the bounty is cancelled before payout.
+	#[pallet::storage]
+	pub type BountyContributions<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		BountyIndex,
+		Twox64Concat,
+		T::AccountId,
+		BalanceOf<T>,
+		ValueQuery,
+	>;
+
+	/// The block an `Active` bounty's curator last confirmed they're still working it, via either
+	/// [`Pallet::curator_heartbeat`] or being assigned the curator role in the first place.
+	/// Absence for longer than the bounty's `activity_period` makes it eligible for
+	/// [`Pallet::slash_inactive_curator`].
+	#[pallet::storage]
+	pub type LastActivity<T: Config> =
+		StorageMap<_, Twox64Concat, BountyIndex, BlockNumberFor<T>, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// New bounty proposal.
+		BountyProposed {
+			/// The index of the bounty proposed.
+			index: BountyIndex,
+		},
+		/// A bounty proposal is funded and became active.
+		BountyBecameActive {
+			/// The index of the bounty that became active.
+			index: BountyIndex,
+		},
+		/// A bounty is awarded to a beneficiary.
+		BountyAwarded {
+			/// The index of the bounty that was awarded.
+			index: BountyIndex,
+			/// The account that received the award.
+			beneficiary: T::AccountId,
+		},
+		/// A bounty is claimed by beneficiary.
+		BountyClaimed {
+			/// The index of the bounty that was claimed.
+			index: BountyIndex,
+			/// The amount of the payout.
+			payout: BalanceOf<T>,
+			/// The account that claimed it.
+			beneficiary: T::AccountId,
+		},
+		/// A bounty is cancelled.
+		BountyCanceled {
+			/// The index of the bounty that was cancelled.
+			index: BountyIndex,
+		},
+		/// An account topped up a bounty's value.
+		BountyContributed {
+			/// The bounty that was topped up.
+			index: BountyIndex,
+			/// The account that contributed.
+			contributor: T::AccountId,
+			/// The amount contributed.
+			amount: BalanceOf<T>,
+		},
+		/// A bounty's curator was slashed and unassigned for going quiet for too long.
+		CuratorSlashed {
+			/// The bounty whose curator was slashed.
+			index: BountyIndex,
+			/// The curator that was slashed.
+			curator: T::AccountId,
+		},
+		/// A milestone was awarded to a beneficiary.
+		MilestoneAwarded {
+			/// The bounty whose milestone was awarded.
+			index: BountyIndex,
+			/// The milestone awarded within that bounty.
+			milestone: MilestoneIndex,
+		},
+		/// A milestone was claimed by its beneficiary.
+		MilestoneClaimed {
+			/// The bounty whose milestone was claimed.
+			index: BountyIndex,
+			/// The milestone claimed within that bounty.
+			milestone: MilestoneIndex,
+			/// The amount of the payout.
+			payout: BalanceOf<T>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// No proposal or bounty at that index.
+		InvalidIndex,
+		/// The reason given is just too big.
+		ReasonTooBig,
+		/// The bounty status is unexpected.
+		UnexpectedStatus,
+		/// Require bounty curator.
+		RequireCurator,
+		/// Invalid bounty value.
+		InvalidValue,
+		/// A bounty payout is pending. To cancel the bounty, you must unassign and slash the
+		/// curator.
+		PendingPayout,
+		/// Milestones must sum to no more than the bounty's value.
+		MilestonesExceedValue,
+		/// No such milestone exists.
+		InvalidMilestone,
+		/// The milestone has already been claimed.
+		MilestoneAlreadyClaimed,
+		/// The milestone is not yet due.
+		MilestoneNotDue,
+		/// The curator has not been inactive for long enough to be slashed.
+		CuratorNotInactive,
+		/// A bounty can only be cancelled before it has been assigned a curator.
+		BountyNotCancellable,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Award bounty to a beneficiary account. The beneficiary will be able to claim the funds
+		/// after a delay.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::award_bounty())]
+		pub fn award_bounty(
+			origin: OriginFor<T>,
+			#[pallet::compact] bounty_id: BountyIndex,
+			beneficiary: T::AccountId,
+		) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			Self::do_award_bounty(signer, bounty_id, beneficiary)
+		}
+
+		/// Claim the payout from an awarded bounty after the payout delay.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::claim_bounty())]
+		pub fn claim_bounty(
+			origin: OriginFor<T>,
+			#[pallet::compact] bounty_id: BountyIndex,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::do_claim_bounty(bounty_id)
+		}
+
+		/// Add milestones to a bounty, splitting its payout into installments.
+		///
+		/// The sum of all milestone amounts must not exceed the bounty's value, and the bounty
+		/// remains `Active` (rather than moving to `PendingPayout`) until every milestone has been
+		/// claimed; the single-shot `award_bounty`/`claim_bounty` path remains available for
+		/// bounties with no milestones.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::propose_milestones(milestones.len() as u32))]
+		pub fn propose_milestones(
+			origin: OriginFor<T>,
+			#[pallet::compact] bounty_id: BountyIndex,
+			milestones: BoundedVec<(BalanceOf<T>, T::AccountId), ConstU32<10>>,
+		) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			Self::do_propose_milestones(signer, bounty_id, milestones)
+		}
+
+		/// Award a single milestone of a bounty to its beneficiary, starting its payout delay.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::award_milestone())]
+		pub fn award_milestone(
+			origin: OriginFor<T>,
+			#[pallet::compact] bounty_id: BountyIndex,
+			milestone: MilestoneIndex,
+		) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			Self::do_award_milestone(signer, bounty_id, milestone)
+		}
+
+		/// Claim the payout of an awarded milestone after its delay has passed.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::claim_milestone())]
+		pub fn claim_milestone(
+			origin: OriginFor<T>,
+			#[pallet::compact] bounty_id: BountyIndex,
+			milestone: MilestoneIndex,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::do_claim_milestone(bounty_id, milestone)
+		}
+
+		/// Top up a bounty's value with additional funds from the caller.
+		///
+		/// Anyone may contribute, not just the original proposer; contributions are tracked per
+		/// account in [`BountyContributions`] so they can be refunded if the bounty is cancelled
+		/// before being awarded.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::add_bounty_funds())]
+		pub fn add_bounty_funds(
+			origin: OriginFor<T>,
+			#[pallet::compact] bounty_id: BountyIndex,
+			#[pallet::compact] amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			Self::do_add_bounty_funds(signer, bounty_id, amount)
+		}
+
+		/// Cancel a bounty that has not yet been assigned a curator, refunding every contribution
+		/// made to it via [`Pallet::add_bounty_funds`].
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::close_bounty_proposed())]
+		pub fn cancel_bounty(
+			origin: OriginFor<T>,
+			#[pallet::compact] bounty_id: BountyIndex,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::do_cancel_bounty(bounty_id)
+		}
+
+		/// Propose a curator for a funded bounty, along with the fee they'll be paid out of it.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::propose_curator())]
+		pub fn propose_curator(
+			origin: OriginFor<T>,
+			#[pallet::compact] bounty_id: BountyIndex,
+			curator: T::AccountId,
+			#[pallet::compact] fee: BalanceOf<T>,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			Self::do_propose_curator(bounty_id, curator, fee)
+		}
+
+		/// Accept being proposed as a bounty's curator, lodging the curator deposit.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::accept_curator())]
+		pub fn accept_curator(
+			origin: OriginFor<T>,
+			#[pallet::compact] bounty_id: BountyIndex,
+		) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			Self::do_accept_curator(signer, bounty_id)
+		}
+
+		/// Unassign a bounty's curator, releasing their deposit and returning the bounty to
+		/// `Funded`.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::unassign_curator())]
+		pub fn unassign_curator(
+			origin: OriginFor<T>,
+			#[pallet::compact] bounty_id: BountyIndex,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::do_unassign_curator(bounty_id)
+		}
+
+		/// Confirm that an active bounty's curator is still working it, resetting the clock
+		/// [`Pallet::slash_inactive_curator`] checks against.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::curator_heartbeat())]
+		pub fn curator_heartbeat(
+			origin: OriginFor<T>,
+			#[pallet::compact] bounty_id: BountyIndex,
+		) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			Self::do_curator_heartbeat(signer, bounty_id)
+		}
+
+		/// Slash and unassign the curator of an active bounty that has gone quiet for longer
+		/// than its `activity_period`. Callable by anyone, to keep stalled bounties from sitting
+		/// unattended indefinitely.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::slash_inactive_curator())]
+		pub fn slash_inactive_curator(
+			origin: OriginFor<T>,
+			#[pallet::compact] bounty_id: BountyIndex,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::do_slash_inactive_curator(bounty_id)
+		}
+
+		/// Propose a new bounty, lodging a deposit from the caller that's returned when the
+		/// bounty is claimed or cancelled.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::propose_bounty(reason.len() as u32))]
+		pub fn propose_bounty(
+			origin: OriginFor<T>,
+			#[pallet::compact] value: BalanceOf<T>,
+			reason: alloc::vec::Vec<u8>,
+		) -> DispatchResult {
+			let signer = ensure_signed(origin)?;
+			Self::do_propose_bounty(signer, value, reason)
+		}
+
+		/// Approve a proposed bounty, queuing it to be funded out of the treasury by the next
+		/// call to [`Pallet::spend_funds`].
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::approve_bounty())]
+		pub fn approve_bounty(
+			origin: OriginFor<T>,
+			#[pallet::compact] bounty_id: BountyIndex,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+			Self::do_approve_bounty(bounty_id)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Fund as many approved bounties as the remaining budget allows, resuming from
+	/// [`BountyApprovalsCursor`] rather than restarting from the front of [`BountyApprovals`]
+	/// each time.
+	///
+	/// Earlier versions of this walked the *entire* `BountyApprovals` backlog on every call,
+	/// which meant the weight (and block time) of a single call scaled with the size of the
+	/// backlog rather than with [`Config::MaxApprovalsPerSpend`]. This processes at most
+	/// `MaxApprovalsPerSpend` entries per call and leaves a cursor behind for the next call to
+	/// pick up where this one left off, preserving the FIFO order bounties were approved in.
+	/// Intended to be driven by pallet-treasury's periodic spend; kept as a plain associated
+	/// function since this checkout has no pallet-treasury dependency to implement a hook trait
+	/// against.
+	pub fn spend_funds(budget_remaining: &mut BalanceOf<T>) -> Weight {
+		let approvals = BountyApprovals::<T>::get();
+		let total = approvals.len();
+		if total == 0 {
+			return T::WeightInfo::spend_funds(0);
+		}
+
+		let start = (BountyApprovalsCursor::<T>::get().unwrap_or(0) as usize).min(total);
+		let max = T::MaxApprovalsPerSpend::get() as usize;
+		let end = total.min(start.saturating_add(max));
+
+		// Approvals this round couldn't afford are kept, not dropped: they're retried the next
+		// time `spend_funds` runs and the budget has recovered, rather than vanishing forever
+		// just because they happened to be examined in a round that came up short.
+		let mut processed = 0u32;
+		let mut carried_over = alloc::vec::Vec::new();
+		for &bounty_id in &approvals[start..end] {
+			processed = processed.saturating_add(1);
+			let Some(bounty_value) = Bounties::<T>::get(bounty_id).map(|b| b.value) else {
+				continue
+			};
+			if bounty_value > *budget_remaining {
+				carried_over.push(bounty_id);
+				continue
+			}
+
+			*budget_remaining = budget_remaining.saturating_sub(bounty_value);
+			Bounties::<T>::mutate_exists(bounty_id, |maybe_bounty| {
+				if let Some(bounty) = maybe_bounty {
+					bounty.status = BountyStatus::Funded;
+				}
+			});
+		}
+
+		let mut remaining = approvals;
+		remaining.splice(start..end, carried_over.iter().copied());
+		if remaining.is_empty() {
+			BountyApprovals::<T>::kill();
+		} else {
+			BountyApprovals::<T>::put(remaining);
+		}
+		if end >= total {
+			BountyApprovalsCursor::<T>::kill();
+		} else {
+			BountyApprovalsCursor::<T>::put((start.saturating_add(carried_over.len())) as u32);
+		}
+
+		T::WeightInfo::spend_funds(processed)
+	}
+
+	fn bounty_account_id(id: BountyIndex) -> T::AccountId {
+		// Each bounty's escrow is a sub-account of the pallet's `PalletId`, derived from the
+		// bounty index so that every bounty holds its funds in its own account.
+		T::PalletId::get().into_sub_account_truncating(("bt", id))
+	}
+
+	/// Award a bounty to a beneficiary, starting its payout delay.
+	pub fn do_award_bounty(
+		signer: T::AccountId,
+		bounty_id: BountyIndex,
+		beneficiary: T::AccountId,
+	) -> DispatchResult {
+		Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+			let bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+			match &bounty.status {
+				BountyStatus::Active { curator, .. } => {
+					ensure!(*curator == signer, Error::<T>::RequireCurator);
+				},
+				_ => return Err(Error::<T>::UnexpectedStatus.into()),
+			}
+
+			let curator = signer;
+			let unlock_at = frame_system::Pallet::<T>::block_number()
+				.saturating_add(T::DefaultActivityPeriod::get());
+			bounty.status =
+				BountyStatus::PendingPayout { curator, beneficiary: beneficiary.clone(), unlock_at };
+
+			Ok(())
+		})?;
+		LastActivity::<T>::remove(bounty_id);
+
+		Self::deposit_event(Event::BountyAwarded { index: bounty_id, beneficiary });
+		Ok(())
+	}
+
+	/// Pay out an awarded bounty once its payout delay has elapsed.
+	pub fn do_claim_bounty(bounty_id: BountyIndex) -> DispatchResult {
+		Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+			let bounty = maybe_bounty.take().ok_or(Error::<T>::InvalidIndex)?;
+			let (curator, beneficiary, unlock_at) = match bounty.status {
+				BountyStatus::PendingPayout { curator, beneficiary, unlock_at } =>
+					(curator, beneficiary, unlock_at),
+				_ => return Err(Error::<T>::UnexpectedStatus.into()),
+			};
+			ensure!(
+				frame_system::Pallet::<T>::block_number() >= unlock_at,
+				Error::<T>::PendingPayout
+			);
+
+			let bounty_account = Self::bounty_account_id(bounty_id);
+			let payout = bounty.value.saturating_sub(bounty.fee);
+
+			let _ = T::Currency::unreserve(&curator, bounty.curator_deposit);
+			let _ = T::Currency::unreserve(&bounty.proposer, bounty.bond);
+			let _ = T::Currency::transfer(&bounty_account, &curator, bounty.fee, AllowDeath);
+			let _ = T::Currency::transfer(&bounty_account, &beneficiary, payout, AllowDeath);
+
+			BountyDescriptions::<T>::remove(bounty_id);
+
+			Self::deposit_event(Event::BountyClaimed { index: bounty_id, payout, beneficiary });
+			Ok(())
+		})
+	}
+
+	/// Attach milestones to a bounty. Only the bounty's curator may do this, and only while no
+	/// payout for the bounty (or any of its milestones) is outstanding.
+	pub fn do_propose_milestones(
+		signer: T::AccountId,
+		bounty_id: BountyIndex,
+		milestones: BoundedVec<(BalanceOf<T>, T::AccountId), ConstU32<10>>,
+	) -> DispatchResult {
+		let bounty = Bounties::<T>::get(bounty_id).ok_or(Error::<T>::InvalidIndex)?;
+		match &bounty.status {
+			BountyStatus::Active { curator, .. } => ensure!(*curator == signer, Error::<T>::RequireCurator),
+			_ => return Err(Error::<T>::UnexpectedStatus.into()),
+		}
+		let payout_outstanding = BountyMilestones::<T>::get(bounty_id)
+			.iter()
+			.any(|m| !m.claimed && m.unlock_block != BlockNumberFor::<T>::max_value());
+		ensure!(!payout_outstanding, Error::<T>::PendingPayout);
+
+		let total: BalanceOf<T> =
+			milestones.iter().fold(BalanceOf::<T>::zero(), |acc, (amount, _)| acc.saturating_add(*amount));
+		ensure!(total <= bounty.value, Error::<T>::MilestonesExceedValue);
+
+		// A milestone is not claimable until `award_milestone` sets its real unlock block.
+		let records: BoundedVec<_, ConstU32<10>> = milestones
+			.into_iter()
+			.map(|(amount, beneficiary)| Milestone {
+				amount,
+				beneficiary,
+				unlock_block: BlockNumberFor::<T>::max_value(),
+				claimed: false,
+			})
+			.collect::<alloc::vec::Vec<_>>()
+			.try_into()
+			.map_err(|_| Error::<T>::MilestonesExceedValue)?;
+
+		BountyMilestones::<T>::insert(bounty_id, records);
+		Ok(())
+	}
+
+	/// Award a single milestone, starting its own payout delay.
+	pub fn do_award_milestone(
+		signer: T::AccountId,
+		bounty_id: BountyIndex,
+		milestone: MilestoneIndex,
+	) -> DispatchResult {
+		let bounty = Bounties::<T>::get(bounty_id).ok_or(Error::<T>::InvalidIndex)?;
+		match &bounty.status {
+			BountyStatus::Active { curator, .. } => ensure!(*curator == signer, Error::<T>::RequireCurator),
+			_ => return Err(Error::<T>::UnexpectedStatus.into()),
+		}
+
+		BountyMilestones::<T>::try_mutate(bounty_id, |milestones| -> DispatchResult {
+			let record =
+				milestones.get_mut(milestone as usize).ok_or(Error::<T>::InvalidMilestone)?;
+			ensure!(!record.claimed, Error::<T>::MilestoneAlreadyClaimed);
+			record.unlock_block = frame_system::Pallet::<T>::block_number()
+				.saturating_add(T::DefaultActivityPeriod::get());
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::MilestoneAwarded { index: bounty_id, milestone });
+		Ok(())
+	}
+
+	/// Pay out a single awarded milestone once its delay has elapsed. The bounty as a whole
+	/// remains `Active` until every milestone has been claimed.
+	pub fn do_claim_milestone(bounty_id: BountyIndex, milestone: MilestoneIndex) -> DispatchResult {
+		let bounty = Bounties::<T>::get(bounty_id).ok_or(Error::<T>::InvalidIndex)?;
+		let bounty_account = Self::bounty_account_id(bounty_id);
+
+		let (beneficiary, payout) =
+			BountyMilestones::<T>::try_mutate(bounty_id, |milestones| -> Result<_, DispatchError> {
+				let record =
+					milestones.get_mut(milestone as usize).ok_or(Error::<T>::InvalidMilestone)?;
+				ensure!(!record.claimed, Error::<T>::MilestoneAlreadyClaimed);
+				ensure!(
+					frame_system::Pallet::<T>::block_number() >= record.unlock_block,
+					Error::<T>::MilestoneNotDue
+				);
+				record.claimed = true;
+				Ok((record.beneficiary.clone(), record.amount))
+			})?;
+
+		let _ = T::Currency::transfer(&bounty_account, &beneficiary, payout, AllowDeath);
+		let _ = &bounty;
+
+		Self::deposit_event(Event::MilestoneClaimed { index: bounty_id, milestone, payout });
+		Ok(())
+	}
+
+	/// Move funds from `who` into the bounty's escrow account and record the contribution.
+	pub fn do_add_bounty_funds(
+		who: T::AccountId,
+		bounty_id: BountyIndex,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		Bounties::<T>::try_mutate(bounty_id, |maybe_bounty| -> DispatchResult {
+			let bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+			let bounty_account = Self::bounty_account_id(bounty_id);
+			T::Currency::transfer(&who, &bounty_account, amount, AllowDeath)?;
+			bounty.value = bounty.value.saturating_add(amount);
+			Ok(())
+		})?;
+
+		BountyContributions::<T>::mutate(bounty_id, &who, |total| {
+			*total = total.saturating_add(amount);
+		});
+
+		Self::deposit_event(Event::BountyContributed { index: bounty_id, contributor: who, amount });
+		Ok(())
+	}
+
+	/// Propose a new bounty, lodging `BountyDepositBase + DataDepositPerByte * reason.len()` as
+	/// `bond` from `proposer` and leaving it `Proposed` until [`Pallet::approve_bounty`] moves it
+	/// along.
+	pub fn do_propose_bounty(
+		proposer: T::AccountId,
+		value: BalanceOf<T>,
+		reason: alloc::vec::Vec<u8>,
+	) -> DispatchResult {
+		ensure!(value >= T::BountyValueMinimum::get(), Error::<T>::InvalidValue);
+		let bounded_reason: BoundedVec<u8, T::MaximumReasonLength> =
+			reason.try_into().map_err(|_| Error::<T>::ReasonTooBig)?;
+
+		let bond = T::BountyDepositBase::get()
+			.saturating_add(T::DataDepositPerByte::get().saturating_mul((bounded_reason.len() as u32).into()));
+		T::Currency::reserve(&proposer, bond)?;
+
+		let index = BountyCount::<T>::get();
+		BountyCount::<T>::put(index.saturating_add(1));
+
+		Bounties::<T>::insert(
+			index,
+			Bounty {
+				proposer,
+				value,
+				fee: Zero::zero(),
+				curator_deposit: Zero::zero(),
+				bond,
+				status: BountyStatus::Proposed,
+				activity_period: T::DefaultActivityPeriod::get(),
+			},
+		);
+		BountyDescriptions::<T>::insert(index, bounded_reason);
+
+		Self::deposit_event(Event::BountyProposed { index });
+		Ok(())
+	}
+
+	/// Approve a `Proposed` bounty, queuing it in [`BountyApprovals`] to be funded by the next
+	/// call to [`Pallet::spend_funds`].
+	pub fn do_approve_bounty(bounty_id: BountyIndex) -> DispatchResult {
+		Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+			let bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+			ensure!(bounty.status == BountyStatus::Proposed, Error::<T>::UnexpectedStatus);
+
+			bounty.status = BountyStatus::Approved;
+			BountyApprovals::<T>::append(bounty_id);
+			Ok(())
+		})
+	}
+
+	/// Cancel a bounty that has not yet been assigned a curator, refunding every contributor
+	/// pro-rata (in practice, in full, since each contributor's share of the escrowed balance
+	/// always equals what they put in).
+	pub fn do_cancel_bounty(bounty_id: BountyIndex) -> DispatchResult {
+		let bounty = Bounties::<T>::get(bounty_id).ok_or(Error::<T>::InvalidIndex)?;
+		ensure!(
+			matches!(bounty.status, BountyStatus::Proposed | BountyStatus::Approved | BountyStatus::Funded),
+			Error::<T>::BountyNotCancellable
+		);
+
+		Self::do_refund_contributions(bounty_id);
+		let _ = T::Currency::unreserve(&bounty.proposer, bounty.bond);
+
+		Bounties::<T>::remove(bounty_id);
+		BountyDescriptions::<T>::remove(bounty_id);
+		BountyMilestones::<T>::remove(bounty_id);
+
+		Self::deposit_event(Event::BountyCanceled { index: bounty_id });
+		Ok(())
+	}
+
+	/// Refund every recorded contribution to a bounty out of its escrow account, clearing
+	/// [`BountyContributions`] for that bounty as it goes.
+	fn do_refund_contributions(bounty_id: BountyIndex) {
+		let bounty_account = Self::bounty_account_id(bounty_id);
+		BountyContributions::<T>::drain_prefix(bounty_id).for_each(|(contributor, amount)| {
+			let _ = T::Currency::transfer(&bounty_account, &contributor, amount, AllowDeath);
+		});
+	}
+
+	/// The deposit a curator must lodge to accept a bounty paying `fee`, as
+	/// `fee * CuratorDepositMultiplier` clamped to `[CuratorDepositMin, CuratorDepositMax]`.
+	pub fn calculate_curator_deposit(fee: &BalanceOf<T>) -> BalanceOf<T> {
+		let mut deposit = T::CuratorDepositMultiplier::get() * *fee;
+
+		if let Some(max_deposit) = T::CuratorDepositMax::get() {
+			deposit = deposit.min(max_deposit);
+		}
+
+		if let Some(min_deposit) = T::CuratorDepositMin::get() {
+			deposit = deposit.max(min_deposit);
+		}
+
+		deposit
+	}
+
+	/// Propose `curator` to take on a funded bounty for `fee`.
+	pub fn do_propose_curator(
+		bounty_id: BountyIndex,
+		curator: T::AccountId,
+		fee: BalanceOf<T>,
+	) -> DispatchResult {
+		Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+			let bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+			ensure!(bounty.status == BountyStatus::Funded, Error::<T>::UnexpectedStatus);
+			ensure!(fee <= bounty.value, Error::<T>::InvalidValue);
+
+			bounty.fee = fee;
+			bounty.status = BountyStatus::CuratorProposed { curator };
+			Ok(())
+		})
+	}
+
+	/// Accept a proposed curator role, becoming `Active`.
+	///
+	/// The curator deposit itself is reserved by [`CheckCuratorDeposit::prepare`] before this
+	/// dispatches, not here — this only records the amount the bounty is on the hook for so
+	/// [`CheckCuratorDeposit::post_dispatch`] knows how much to release later.
+	pub fn do_accept_curator(signer: T::AccountId, bounty_id: BountyIndex) -> DispatchResult {
+		Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+			let bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+			match &bounty.status {
+				BountyStatus::CuratorProposed { curator } => {
+					ensure!(*curator == signer, Error::<T>::RequireCurator);
+				},
+				_ => return Err(Error::<T>::UnexpectedStatus.into()),
+			}
+
+			bounty.curator_deposit = Self::calculate_curator_deposit(&bounty.fee);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let update_due = now.saturating_add(bounty.activity_period);
+			bounty.status = BountyStatus::Active { curator: signer, update_due };
+			LastActivity::<T>::insert(bounty_id, now);
+			Ok(())
+		})
+	}
+
+	/// Release a bounty's curator and return the bounty to `Funded`.
+	///
+	/// The deposit refund itself happens in [`CheckCuratorDeposit::post_dispatch`], for the
+	/// `bounty.curator_deposit` that [`CheckCuratorDeposit::prepare`] read before this call zeroes
+	/// it out.
+	pub fn do_unassign_curator(bounty_id: BountyIndex) -> DispatchResult {
+		Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+			let bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+			match &bounty.status {
+				BountyStatus::CuratorProposed { .. } => {},
+				BountyStatus::Active { .. } => {},
+				_ => return Err(Error::<T>::UnexpectedStatus.into()),
+			};
+
+			bounty.curator_deposit = Zero::zero();
+			bounty.status = BountyStatus::Funded;
+			LastActivity::<T>::remove(bounty_id);
+			Ok(())
+		})
+	}
+
+	/// Record that `signer`, as an active bounty's curator, is still working it.
+	pub fn do_curator_heartbeat(signer: T::AccountId, bounty_id: BountyIndex) -> DispatchResult {
+		let bounty = Bounties::<T>::get(bounty_id).ok_or(Error::<T>::InvalidIndex)?;
+		match &bounty.status {
+			BountyStatus::Active { curator, .. } => ensure!(*curator == signer, Error::<T>::RequireCurator),
+			_ => return Err(Error::<T>::UnexpectedStatus.into()),
+		}
+
+		LastActivity::<T>::insert(bounty_id, frame_system::Pallet::<T>::block_number());
+		Ok(())
+	}
+
+	/// Slash and unassign an active bounty's curator once they've gone quiet for longer than the
+	/// bounty's `activity_period`.
+	///
+	/// The slash itself happens in [`CheckCuratorDeposit::post_dispatch`], for the
+	/// `bounty.curator_deposit` that [`CheckCuratorDeposit::prepare`] read before this call zeroes
+	/// it out.
+	pub fn do_slash_inactive_curator(bounty_id: BountyIndex) -> DispatchResult {
+		Bounties::<T>::try_mutate_exists(bounty_id, |maybe_bounty| -> DispatchResult {
+			let bounty = maybe_bounty.as_mut().ok_or(Error::<T>::InvalidIndex)?;
+			let curator = match &bounty.status {
+				BountyStatus::Active { curator, .. } => curator.clone(),
+				_ => return Err(Error::<T>::UnexpectedStatus.into()),
+			};
+
+			let last_activity = LastActivity::<T>::get(bounty_id).unwrap_or_default();
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				now.saturating_sub(last_activity) > bounty.activity_period,
+				Error::<T>::CuratorNotInactive
+			);
+
+			bounty.curator_deposit = Zero::zero();
+			bounty.status = BountyStatus::Funded;
+			LastActivity::<T>::remove(bounty_id);
+
+			Self::deposit_event(Event::CuratorSlashed { index: bounty_id, curator });
+			Ok(())
+		})
+	}
+}
+
+/// What, if anything, [`CheckCuratorDeposit::post_dispatch`] still needs to do to the curator
+/// deposit once dispatch has run.
+pub enum CuratorDepositAction<AccountId, Balance> {
+	/// Nothing to do; the dispatched call doesn't touch the curator deposit.
+	None,
+	/// `accept_curator` reserved `amount` from `who` in [`CheckCuratorDeposit::prepare`]; if
+	/// dispatch failed, give it back.
+	Hold { who: AccountId, amount: Balance },
+	/// `unassign_curator` dispatched successfully; release `amount` back to `who`.
+	Release { who: AccountId, amount: Balance },
+	/// `slash_inactive_curator` dispatched successfully; slash `amount` from `who`.
+	Slash { who: AccountId, amount: Balance },
+}
+
+/// A [`TransactionExtension`] that owns the curator deposit's actual custody: it reserves the
+/// deposit when a curator accepts a bounty, and releases or slashes it once `unassign_curator` or
+/// `slash_inactive_curator` dispatch, rather than leaving those `reserve`/`unreserve`/
+/// `slash_reserved` calls scattered across the `do_*` functions themselves.
+///
+/// `validate` additionally rejects `accept_curator` calls up front when the signer plainly cannot
+/// cover the deposit, instead of letting the transaction into a block only to fail inside
+/// `prepare`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckCuratorDeposit<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> CheckCuratorDeposit<T> {
+	/// Construct a new instance.
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T: Config + Send + Sync> Default for CheckCuratorDeposit<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Config + Send + Sync> core::fmt::Debug for CheckCuratorDeposit<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+		write!(f, "CheckCuratorDeposit")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut core::fmt::Formatter) -> core::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> TransactionExtension<T::RuntimeCall> for CheckCuratorDeposit<T>
+where
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo>,
+{
+	const IDENTIFIER: &'static str = "CheckCuratorDeposit";
+	type Implicit = ();
+	type Val = ();
+	type Pre = CuratorDepositAction<T::AccountId, BalanceOf<T>>;
+
+	fn weight(&self, _call: &T::RuntimeCall) -> Weight {
+		T::WeightInfo::check_curator_deposit_validate()
+	}
+
+	fn validate(
+		&self,
+		origin: <T::RuntimeCall as Dispatchable>::RuntimeOrigin,
+		call: &T::RuntimeCall,
+		_info: &DispatchInfo,
+		_len: usize,
+		_self_implicit: Self::Implicit,
+		_inherited_implication: &impl Encode,
+		_source: sp_runtime::transaction_validity::TransactionSource,
+	) -> Result<
+		(ValidTransaction, Self::Val, <T::RuntimeCall as Dispatchable>::RuntimeOrigin),
+		TransactionValidityError,
+	> {
+		if let Some(Call::<T>::accept_curator { bounty_id }) = call.is_sub_type() {
+			let who = ensure_signed(origin.clone())
+				.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::BadSigner))?;
+			let bounty = Bounties::<T>::get(bounty_id)
+				.ok_or(TransactionValidityError::Invalid(InvalidTransaction::Call))?;
+			let deposit = Pallet::<T>::calculate_curator_deposit(&bounty.fee);
+			ensure!(
+				T::Currency::free_balance(&who) >= deposit,
+				TransactionValidityError::Invalid(InvalidTransaction::Payment)
+			);
+		}
+
+		Ok((ValidTransaction::default(), (), origin))
+	}
+
+	fn prepare(
+		self,
+		_val: Self::Val,
+		origin: &<T::RuntimeCall as Dispatchable>::RuntimeOrigin,
+		call: &T::RuntimeCall,
+		_info: &DispatchInfo,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		match call.is_sub_type() {
+			Some(Call::<T>::accept_curator { bounty_id }) => {
+				let who = ensure_signed(origin.clone())
+					.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::BadSigner))?;
+				let bounty = Bounties::<T>::get(bounty_id)
+					.ok_or(TransactionValidityError::Invalid(InvalidTransaction::Call))?;
+				let amount = Pallet::<T>::calculate_curator_deposit(&bounty.fee);
+				T::Currency::reserve(&who, amount)
+					.map_err(|_| TransactionValidityError::Invalid(InvalidTransaction::Payment))?;
+				Ok(CuratorDepositAction::Hold { who, amount })
+			},
+			Some(Call::<T>::unassign_curator { bounty_id }) => {
+				let bounty = Bounties::<T>::get(bounty_id)
+					.ok_or(TransactionValidityError::Invalid(InvalidTransaction::Call))?;
+				let who = match bounty.status {
+					BountyStatus::CuratorProposed { curator } => curator,
+					BountyStatus::Active { curator, .. } => curator,
+					_ => return Ok(CuratorDepositAction::None),
+				};
+				Ok(CuratorDepositAction::Release { who, amount: bounty.curator_deposit })
+			},
+			Some(Call::<T>::slash_inactive_curator { bounty_id }) => {
+				let bounty = Bounties::<T>::get(bounty_id)
+					.ok_or(TransactionValidityError::Invalid(InvalidTransaction::Call))?;
+				let who = match bounty.status {
+					BountyStatus::Active { curator, .. } => curator,
+					_ => return Ok(CuratorDepositAction::None),
+				};
+				Ok(CuratorDepositAction::Slash { who, amount: bounty.curator_deposit })
+			},
+			_ => Ok(CuratorDepositAction::None),
+		}
+	}
+
+	fn post_dispatch(
+		pre: Self::Pre,
+		_info: &DispatchInfo,
+		_post_info: &frame_support::dispatch::PostDispatchInfo,
+		_len: usize,
+		result: &DispatchResult,
+	) -> Result<Weight, TransactionValidityError> {
+		match pre {
+			CuratorDepositAction::None => {},
+			// Dispatch failed to move into `Active`, so the reserve this extension took in
+			// `prepare` is never recorded anywhere; give it back.
+			CuratorDepositAction::Hold { who, amount } =>
+				if result.is_err() {
+					let _ = T::Currency::unreserve(&who, amount);
+				},
+			CuratorDepositAction::Release { who, amount } =>
+				if result.is_ok() {
+					let _ = T::Currency::unreserve(&who, amount);
+				},
+			CuratorDepositAction::Slash { who, amount } =>
+				if result.is_ok() {
+					let (slashed, _remainder) = T::Currency::slash_reserved(&who, amount);
+					drop(slashed);
+				},
+		}
+		Ok(T::WeightInfo::check_curator_deposit_post_dispatch())
+	}
+}
@@ -63,6 +63,14 @@ pub trait WeightInfo {
 	fn close_bounty_active() -> Weight;
 	fn extend_bounty_expiry() -> Weight;
 	fn spend_funds(b: u32, ) -> Weight;
+	fn propose_milestones(m: u32, ) -> Weight;
+	fn award_milestone() -> Weight;
+	fn claim_milestone() -> Weight;
+	fn add_bounty_funds() -> Weight;
+	fn check_curator_deposit_validate() -> Weight;
+	fn check_curator_deposit_post_dispatch() -> Weight;
+	fn slash_inactive_curator() -> Weight;
+	fn curator_heartbeat() -> Weight;
 }
 
 /// Weights for pallet_bounties using the Substrate node and recommended hardware.
@@ -174,6 +182,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: ChildBounties ParentChildBounties (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
 	/// Storage: System Account (r:1 w:1)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Bounties BountyContributions (r:1 w:1)
+	/// Proof: Bounties BountyContributions (max_values: None, max_size: Some(802), added: 3277, mode: MaxEncodedLen)
 	/// Storage: Bounties BountyDescriptions (r:0 w:1)
 	/// Proof: Bounties BountyDescriptions (max_values: None, max_size: Some(314), added: 2789, mode: MaxEncodedLen)
 	fn close_bounty_proposed() -> Weight {
@@ -182,8 +192,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		//  Estimated: `3642`
 		// Minimum execution time: 38_200_000 picoseconds.
 		Weight::from_parts(39_698_000, 3642)
-			.saturating_add(T::DbWeight::get().reads(3_u64))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
 	/// Storage: Bounties Bounties (r:1 w:1)
 	/// Proof: Bounties Bounties (max_values: None, max_size: Some(177), added: 2652, mode: MaxEncodedLen)
@@ -191,6 +201,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: ChildBounties ParentChildBounties (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
 	/// Storage: System Account (r:2 w:2)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Bounties BountyContributions (r:1 w:1)
+	/// Proof: Bounties BountyContributions (max_values: None, max_size: Some(802), added: 3277, mode: MaxEncodedLen)
 	/// Storage: Bounties BountyDescriptions (r:0 w:1)
 	/// Proof: Bounties BountyDescriptions (max_values: None, max_size: Some(314), added: 2789, mode: MaxEncodedLen)
 	fn close_bounty_active() -> Weight {
@@ -199,8 +211,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		//  Estimated: `6196`
 		// Minimum execution time: 88_427_000 picoseconds.
 		Weight::from_parts(90_307_000, 6196)
-			.saturating_add(T::DbWeight::get().reads(4_u64))
-			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
 	/// Storage: Bounties Bounties (r:1 w:1)
 	/// Proof: Bounties Bounties (max_values: None, max_size: Some(177), added: 2652, mode: MaxEncodedLen)
@@ -215,11 +227,14 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	}
 	/// Storage: Bounties BountyApprovals (r:1 w:1)
 	/// Proof: Bounties BountyApprovals (max_values: Some(1), max_size: Some(402), added: 897, mode: MaxEncodedLen)
+	/// Storage: Bounties BountyApprovalsCursor (r:1 w:1)
+	/// Proof: Bounties BountyApprovalsCursor (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	/// Storage: Bounties Bounties (r:100 w:100)
 	/// Proof: Bounties Bounties (max_values: None, max_size: Some(177), added: 2652, mode: MaxEncodedLen)
 	/// Storage: System Account (r:200 w:200)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
-	/// The range of component `b` is `[0, 100]`.
+	/// `b` is now bounded by `T::MaxApprovalsPerSpend` rather than the full approval backlog; the
+	/// range of component `b` is `[0, T::MaxApprovalsPerSpend::get()]`.
 	fn spend_funds(b: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `4 + b * (297 ±0)`
@@ -228,12 +243,123 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(12_907_786, 1887)
 			// Standard Error: 34_191
 			.saturating_add(Weight::from_parts(46_347_772, 0).saturating_mul(b.into()))
-			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(b.into())))
-			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
 			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(b.into())))
 			.saturating_add(Weight::from_parts(0, 5206).saturating_mul(b.into()))
 	}
+	/// Storage: Bounties Bounties (r:1 w:0)
+	/// Proof: Bounties Bounties (max_values: None, max_size: Some(177), added: 2652, mode: MaxEncodedLen)
+	/// Storage: Bounties BountyMilestones (r:1 w:1)
+	/// Proof: Bounties BountyMilestones (max_values: None, max_size: Some(802), added: 3277, mode: MaxEncodedLen)
+	/// The range of component `m` is `[0, 10]`.
+	fn propose_milestones(m: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `388`
+		//  Estimated: `4267`
+		// Minimum execution time: 11_200_000 picoseconds.
+		Weight::from_parts(11_845_000, 4267)
+			// Standard Error: 1_800
+			.saturating_add(Weight::from_parts(412_000, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Bounties Bounties (r:1 w:0)
+	/// Proof: Bounties Bounties (max_values: None, max_size: Some(177), added: 2652, mode: MaxEncodedLen)
+	/// Storage: Bounties BountyMilestones (r:1 w:1)
+	/// Proof: Bounties BountyMilestones (max_values: None, max_size: Some(802), added: 3277, mode: MaxEncodedLen)
+	fn award_milestone() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `430`
+		//  Estimated: `4267`
+		// Minimum execution time: 12_400_000 picoseconds.
+		Weight::from_parts(12_956_000, 4267)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Bounties Bounties (r:1 w:1)
+	/// Proof: Bounties Bounties (max_values: None, max_size: Some(177), added: 2652, mode: MaxEncodedLen)
+	/// Storage: Bounties BountyMilestones (r:1 w:1)
+	/// Proof: Bounties BountyMilestones (max_values: None, max_size: Some(802), added: 3277, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn claim_milestone() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `512`
+		//  Estimated: `6870`
+		// Minimum execution time: 35_200_000 picoseconds.
+		Weight::from_parts(36_318_000, 6870)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Bounties Bounties (r:1 w:1)
+	/// Proof: Bounties Bounties (max_values: None, max_size: Some(177), added: 2652, mode: MaxEncodedLen)
+	/// Storage: Bounties BountyContributions (r:1 w:1)
+	/// Proof: Bounties BountyContributions (max_values: None, max_size: Some(802), added: 3277, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn add_bounty_funds() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `464`
+		//  Estimated: `4267`
+		// Minimum execution time: 23_100_000 picoseconds.
+		Weight::from_parts(23_942_000, 4267)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Bounties Bounties (r:1 w:0)
+	/// Proof: Bounties Bounties (max_values: None, max_size: Some(177), added: 2652, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn check_curator_deposit_validate() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `420`
+		//  Estimated: `3642`
+		// Minimum execution time: 14_600_000 picoseconds.
+		Weight::from_parts(15_183_000, 3642)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn check_curator_deposit_post_dispatch() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `128`
+		//  Estimated: `3593`
+		// Minimum execution time: 9_200_000 picoseconds.
+		Weight::from_parts(9_648_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Bounties Bounties (r:1 w:1)
+	/// Proof: Bounties Bounties (max_values: None, max_size: Some(177), added: 2652, mode: MaxEncodedLen)
+	/// Storage: Bounties LastActivity (r:1 w:1)
+	/// Proof: Bounties LastActivity (max_values: None, max_size: Some(24), added: 2499, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn slash_inactive_curator() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `556`
+		//  Estimated: `3642`
+		// Minimum execution time: 28_400_000 picoseconds.
+		Weight::from_parts(29_312_000, 3642)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Bounties Bounties (r:1 w:0)
+	/// Proof: Bounties Bounties (max_values: None, max_size: Some(177), added: 2652, mode: MaxEncodedLen)
+	/// Storage: Bounties LastActivity (r:1 w:1)
+	/// Proof: Bounties LastActivity (max_values: None, max_size: Some(24), added: 2499, mode: MaxEncodedLen)
+	fn curator_heartbeat() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `400`
+		//  Estimated: `3642`
+		// Minimum execution time: 9_900_000 picoseconds.
+		Weight::from_parts(10_352_000, 3642)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -344,6 +470,8 @@ impl WeightInfo for () {
 	/// Proof: ChildBounties ParentChildBounties (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
 	/// Storage: System Account (r:1 w:1)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Bounties BountyContributions (r:1 w:1)
+	/// Proof: Bounties BountyContributions (max_values: None, max_size: Some(802), added: 3277, mode: MaxEncodedLen)
 	/// Storage: Bounties BountyDescriptions (r:0 w:1)
 	/// Proof: Bounties BountyDescriptions (max_values: None, max_size: Some(314), added: 2789, mode: MaxEncodedLen)
 	fn close_bounty_proposed() -> Weight {
@@ -352,8 +480,8 @@ impl WeightInfo for () {
 		//  Estimated: `3642`
 		// Minimum execution time: 38_200_000 picoseconds.
 		Weight::from_parts(39_698_000, 3642)
-			.saturating_add(RocksDbWeight::get().reads(3_u64))
-			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
 	/// Storage: Bounties Bounties (r:1 w:1)
 	/// Proof: Bounties Bounties (max_values: None, max_size: Some(177), added: 2652, mode: MaxEncodedLen)
@@ -361,6 +489,8 @@ impl WeightInfo for () {
 	/// Proof: ChildBounties ParentChildBounties (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
 	/// Storage: System Account (r:2 w:2)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Bounties BountyContributions (r:1 w:1)
+	/// Proof: Bounties BountyContributions (max_values: None, max_size: Some(802), added: 3277, mode: MaxEncodedLen)
 	/// Storage: Bounties BountyDescriptions (r:0 w:1)
 	/// Proof: Bounties BountyDescriptions (max_values: None, max_size: Some(314), added: 2789, mode: MaxEncodedLen)
 	fn close_bounty_active() -> Weight {
@@ -369,8 +499,8 @@ impl WeightInfo for () {
 		//  Estimated: `6196`
 		// Minimum execution time: 88_427_000 picoseconds.
 		Weight::from_parts(90_307_000, 6196)
-			.saturating_add(RocksDbWeight::get().reads(4_u64))
-			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
 	/// Storage: Bounties Bounties (r:1 w:1)
 	/// Proof: Bounties Bounties (max_values: None, max_size: Some(177), added: 2652, mode: MaxEncodedLen)
@@ -385,11 +515,14 @@ impl WeightInfo for () {
 	}
 	/// Storage: Bounties BountyApprovals (r:1 w:1)
 	/// Proof: Bounties BountyApprovals (max_values: Some(1), max_size: Some(402), added: 897, mode: MaxEncodedLen)
+	/// Storage: Bounties BountyApprovalsCursor (r:1 w:1)
+	/// Proof: Bounties BountyApprovalsCursor (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	/// Storage: Bounties Bounties (r:100 w:100)
 	/// Proof: Bounties Bounties (max_values: None, max_size: Some(177), added: 2652, mode: MaxEncodedLen)
 	/// Storage: System Account (r:200 w:200)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
-	/// The range of component `b` is `[0, 100]`.
+	/// `b` is now bounded by `T::MaxApprovalsPerSpend` rather than the full approval backlog; the
+	/// range of component `b` is `[0, T::MaxApprovalsPerSpend::get()]`.
 	fn spend_funds(b: u32, ) -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `4 + b * (297 ±0)`
@@ -398,10 +531,121 @@ impl WeightInfo for () {
 		Weight::from_parts(12_907_786, 1887)
 			// Standard Error: 34_191
 			.saturating_add(Weight::from_parts(46_347_772, 0).saturating_mul(b.into()))
-			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(b.into())))
-			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
 			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(b.into())))
 			.saturating_add(Weight::from_parts(0, 5206).saturating_mul(b.into()))
 	}
+	/// Storage: Bounties Bounties (r:1 w:0)
+	/// Proof: Bounties Bounties (max_values: None, max_size: Some(177), added: 2652, mode: MaxEncodedLen)
+	/// Storage: Bounties BountyMilestones (r:1 w:1)
+	/// Proof: Bounties BountyMilestones (max_values: None, max_size: Some(802), added: 3277, mode: MaxEncodedLen)
+	/// The range of component `m` is `[0, 10]`.
+	fn propose_milestones(m: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `388`
+		//  Estimated: `4267`
+		// Minimum execution time: 11_200_000 picoseconds.
+		Weight::from_parts(11_845_000, 4267)
+			// Standard Error: 1_800
+			.saturating_add(Weight::from_parts(412_000, 0).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Bounties Bounties (r:1 w:0)
+	/// Proof: Bounties Bounties (max_values: None, max_size: Some(177), added: 2652, mode: MaxEncodedLen)
+	/// Storage: Bounties BountyMilestones (r:1 w:1)
+	/// Proof: Bounties BountyMilestones (max_values: None, max_size: Some(802), added: 3277, mode: MaxEncodedLen)
+	fn award_milestone() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `430`
+		//  Estimated: `4267`
+		// Minimum execution time: 12_400_000 picoseconds.
+		Weight::from_parts(12_956_000, 4267)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Bounties Bounties (r:1 w:1)
+	/// Proof: Bounties Bounties (max_values: None, max_size: Some(177), added: 2652, mode: MaxEncodedLen)
+	/// Storage: Bounties BountyMilestones (r:1 w:1)
+	/// Proof: Bounties BountyMilestones (max_values: None, max_size: Some(802), added: 3277, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn claim_milestone() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `512`
+		//  Estimated: `6870`
+		// Minimum execution time: 35_200_000 picoseconds.
+		Weight::from_parts(36_318_000, 6870)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: Bounties Bounties (r:1 w:1)
+	/// Proof: Bounties Bounties (max_values: None, max_size: Some(177), added: 2652, mode: MaxEncodedLen)
+	/// Storage: Bounties BountyContributions (r:1 w:1)
+	/// Proof: Bounties BountyContributions (max_values: None, max_size: Some(802), added: 3277, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn add_bounty_funds() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `464`
+		//  Estimated: `4267`
+		// Minimum execution time: 23_100_000 picoseconds.
+		Weight::from_parts(23_942_000, 4267)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: Bounties Bounties (r:1 w:0)
+	/// Proof: Bounties Bounties (max_values: None, max_size: Some(177), added: 2652, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn check_curator_deposit_validate() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `420`
+		//  Estimated: `3642`
+		// Minimum execution time: 14_600_000 picoseconds.
+		Weight::from_parts(15_183_000, 3642)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn check_curator_deposit_post_dispatch() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `128`
+		//  Estimated: `3593`
+		// Minimum execution time: 9_200_000 picoseconds.
+		Weight::from_parts(9_648_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Bounties Bounties (r:1 w:1)
+	/// Proof: Bounties Bounties (max_values: None, max_size: Some(177), added: 2652, mode: MaxEncodedLen)
+	/// Storage: Bounties LastActivity (r:1 w:1)
+	/// Proof: Bounties LastActivity (max_values: None, max_size: Some(24), added: 2499, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn slash_inactive_curator() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `556`
+		//  Estimated: `3642`
+		// Minimum execution time: 28_400_000 picoseconds.
+		Weight::from_parts(29_312_000, 3642)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: Bounties Bounties (r:1 w:0)
+	/// Proof: Bounties Bounties (max_values: None, max_size: Some(177), added: 2652, mode: MaxEncodedLen)
+	/// Storage: Bounties LastActivity (r:1 w:1)
+	/// Proof: Bounties LastActivity (max_values: None, max_size: Some(24), added: 2499, mode: MaxEncodedLen)
+	fn curator_heartbeat() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `400`
+		//  Estimated: `3642`
+		// Minimum execution time: 9_900_000 picoseconds.
+		Weight::from_parts(10_352_000, 3642)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
@@ -290,6 +290,57 @@ mod benches {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn list_region() -> Result<(), BenchmarkError> {
+		setup_and_start_sale::<T>()?;
+
+		advance_to::<T>(2);
+
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::set_balance(&caller.clone(), 10u32.into());
+
+		let region = Broker::<T>::do_purchase(caller.clone(), 10u32.into())
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), region, 5u32.into());
+
+		assert_last_event::<T>(
+			Event::RegionListed { region_id: region, seller: caller, min_price: 5u32.into() }
+				.into(),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn fill_region() -> Result<(), BenchmarkError> {
+		setup_and_start_sale::<T>()?;
+
+		advance_to::<T>(2);
+
+		let seller: T::AccountId = whitelisted_caller();
+		T::Currency::set_balance(&seller.clone(), 10u32.into());
+
+		let region = Broker::<T>::do_purchase(seller.clone(), 10u32.into())
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		Broker::<T>::do_list_region(seller.clone(), region, 5u32.into())
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		let buyer: T::AccountId = account("buyer", 0, SEED);
+		T::Currency::set_balance(&buyer.clone(), 5u32.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(buyer.clone()), region, None, None);
+
+		assert_last_event::<T>(
+			Event::RegionSold { region_id: region, seller, buyer, price: 5u32.into() }.into(),
+		);
+
+		Ok(())
+	}
+
 	#[benchmark]
 	fn partition() -> Result<(), BenchmarkError> {
 		setup_and_start_sale::<T>()?;
@@ -529,6 +580,91 @@ mod benches {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn schedule_core_count(
+		n: Linear<1, { MAX_CORE_COUNT.into() }>,
+	) -> Result<(), BenchmarkError> {
+		let admin_origin =
+			T::AdminOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let ramp_blocks: BlockNumberFor<T> = n.try_into().unwrap();
+
+		#[extrinsic_call]
+		_(admin_origin as T::RuntimeOrigin, MAX_CORE_COUNT, ramp_blocks);
+
+		// The ramp starts from a core count of 0, so scheduling a non-zero target with a
+		// positive `ramp_blocks` always registers an in-progress ramp rather than applying
+		// immediately.
+		let ramp = CoreCountRamp::<T>::get().expect("ramp towards a higher target was scheduled");
+		assert_eq!(ramp.target, MAX_CORE_COUNT);
+		assert_last_event::<T>(
+			Event::CoreCountScheduled { target: MAX_CORE_COUNT, current: 0, next_step: ramp.next_step }
+				.into(),
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_auto_renew() -> Result<(), BenchmarkError> {
+		let core = setup_and_start_sale::<T>()?;
+
+		advance_to::<T>(2);
+
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::set_balance(&caller.clone(), 20u32.into());
+
+		let region = Broker::<T>::do_purchase(caller.clone(), 10u32.into())
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		Broker::<T>::do_assign(region, None, 1001, Final)
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		advance_to::<T>(6);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), region.core, Some(caller.clone()));
+
+		assert_last_event::<T>(
+			Event::AutoRenewEnabled { core: region.core, payer: caller }.into(),
+		);
+
+		let _ = core;
+		Ok(())
+	}
+
+	#[benchmark]
+	fn enable_auto_renew() -> Result<(), BenchmarkError> {
+		// Worst case: a core is enrolled for auto-renewal and the renewal is actually
+		// attempted (and succeeds) when sales rotate.
+		setup_and_start_sale::<T>()?;
+
+		advance_to::<T>(2);
+
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::set_balance(&caller.clone(), 20u32.into());
+
+		let region = Broker::<T>::do_purchase(caller.clone(), 10u32.into())
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		Broker::<T>::do_assign(region, None, 1001, Final)
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		advance_to::<T>(6);
+
+		Broker::<T>::do_set_auto_renew(caller.clone(), region.core, Some(caller.clone()))
+			.map_err(|_| BenchmarkError::Weightless)?;
+
+		#[block]
+		{
+			Broker::<T>::process_auto_renewals();
+		}
+
+		let id = AllowedRenewalId { core: region.core, when: 10 };
+		assert!(AllowedRenewals::<T>::get(id).is_some());
+
+		Ok(())
+	}
+
 	// Implements a test for each benchmark. Execute with:
 	// `cargo test -p pallet-broker --features runtime-benchmarks`.
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
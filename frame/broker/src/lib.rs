@@ -0,0 +1,1344 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Brokerage pallet for managing coretime sales and allocations.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod benchmarking;
+pub mod weights;
+
+// `mock` and `tests` modules backing `benchmarking`'s `impl_benchmark_test_suite!` are outside
+// this trimmed checkout.
+
+use alloc::vec::Vec;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{
+		fungible::Mutate,
+		tokens::{Fortitude, Precision, Preservation},
+		Hooks,
+	},
+	weights::Weight,
+	BoundedVec,
+};
+use frame_system::pallet_prelude::*;
+use scale_info::TypeInfo;
+use sp_arithmetic::Perbill;
+use sp_runtime::traits::{SaturatedConversion, Zero};
+pub use weights::WeightInfo;
+
+pub use pallet::*;
+
+/// The index of a core.
+pub type CoreIndex = u16;
+
+/// The identifier of a task (a parachain, typically) assigned to a core.
+pub type TaskId = u32;
+
+/// A timeslice: the smallest tradable unit of coretime.
+pub type Timeslice = u32;
+
+/// The number of bits in a [`CoreMask`].
+pub const CORE_MASK_BITS: usize = 80;
+
+/// A 80-bit bitmap indicating which parts of a core's time a `Region` covers.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo, Debug, Default)]
+pub struct CoreMask([u8; 10]);
+
+impl CoreMask {
+	/// A mask covering the entire core.
+	pub fn complete() -> Self {
+		Self([0xff; 10])
+	}
+}
+
+impl From<u128> for CoreMask {
+	fn from(x: u128) -> Self {
+		let mut bytes = [0u8; 10];
+		bytes.copy_from_slice(&x.to_be_bytes()[6..16]);
+		Self(bytes)
+	}
+}
+
+impl core::ops::BitXor for CoreMask {
+	type Output = Self;
+	fn bitxor(self, rhs: Self) -> Self {
+		let mut out = [0u8; 10];
+		for i in 0..10 {
+			out[i] = self.0[i] ^ rhs.0[i];
+		}
+		Self(out)
+	}
+}
+
+/// The identity of a Region.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo, Debug)]
+pub struct RegionId {
+	/// The timeslice at which this `RegionId` begins.
+	pub begin: Timeslice,
+	/// The index of the core on which this Region's coretime is scheduled.
+	pub core: CoreIndex,
+	/// The coremask that the Region occupies on the core.
+	pub part: CoreMask,
+}
+
+/// Whether a configuration change should happen immediately (provisionally) or only once it is
+/// certain to apply (finally).
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, TypeInfo, Debug)]
+pub enum Finality {
+	/// The change should happen provisionally, and may be reverted if a competing assignment is
+	/// made.
+	Provisional,
+	/// The change is guaranteed to stick.
+	Final,
+}
+
+/// What a core is assigned to do.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, Debug)]
+pub enum CoreAssignment {
+	/// The core is not assigned to anything.
+	Idle,
+	/// The core is assigned to the Instantaneous Coretime Pool.
+	Pool,
+	/// The core is assigned to a task.
+	Task(TaskId),
+}
+
+/// A single item making up a schedule for a core.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, Debug)]
+pub struct ScheduleItem {
+	/// The portion of the core that is assigned this item.
+	pub part: CoreMask,
+	/// What the part of the core is assigned to do.
+	pub assignment: CoreAssignment,
+}
+
+/// The maximum number of items that can make up a core's schedule.
+pub struct MaxScheduleItems;
+impl Get<u32> for MaxScheduleItems {
+	fn get() -> u32 {
+		CORE_MASK_BITS as u32
+	}
+}
+
+/// A schedule of work for a single core.
+pub type Schedule = BoundedVec<ScheduleItem, MaxScheduleItems>;
+
+/// A lease on a task that does not go through the sale system.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo, Debug)]
+pub struct LeaseRecordItem {
+	/// The task that the lease is for.
+	pub task: TaskId,
+	/// The timeslice until the lease runs.
+	pub until: Timeslice,
+}
+
+/// The configuration of this pallet.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo, Debug)]
+pub struct ConfigRecord<BlockNumber> {
+	/// The number of blocks before a region starts that its sale ends.
+	pub advance_notice: BlockNumber,
+	/// The length in blocks of the Interlude Period for forthcoming sales.
+	pub interlude_length: BlockNumber,
+	/// The length in blocks of the Leadin Period for forthcoming sales.
+	pub leadin_length: BlockNumber,
+	/// The proportion of cores available for sale that should be sold in order for the price
+	/// to remain the same in the next sale.
+	pub ideal_bulk_proportion: Perbill,
+	/// An artificial limit to the number of cores that are allowed to be sold.
+	pub limit_cores_offered: Option<CoreIndex>,
+	/// The length in timeslices of a Region.
+	pub region_length: Timeslice,
+	/// The proportional increase in the amount of core time that is offered for sale when a
+	/// core's renewal is due, compared to the amount paid for last time.
+	pub renewal_bump: Perbill,
+	/// The duration, in timeslices, for which a Region remains claimable once it has been
+	/// dropped, after which its associated deposit is lost.
+	pub contribution_timeout: Timeslice,
+}
+
+/// A record of a sale that's happening.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo, Debug)]
+pub struct SaleInfoRecord<Balance, BlockNumber> {
+	/// The first block at which the sale will begin.
+	pub sale_start: BlockNumber,
+	/// The length in blocks of the Leadin Period.
+	pub leadin_length: BlockNumber,
+	/// The price of Bulk Coretime at the beginning of the Leadin Period.
+	pub start_price: Balance,
+	/// The price of Bulk Coretime after the Leadin Period.
+	pub regular_price: Balance,
+	/// The first timeslice of the Regions which are being sold in this sale.
+	pub region_begin: Timeslice,
+	/// The timeslice on which the Regions which are being sold in this sale terminate.
+	pub region_end: Timeslice,
+	/// The number of cores we want to sell, ideally.
+	pub ideal_cores_sold: CoreIndex,
+	/// Number of cores which are/have been offered for sale.
+	pub cores_offered: CoreIndex,
+	/// Number of cores which have been sold so far.
+	pub cores_sold: CoreIndex,
+	/// The price at which the last core was sold, if any cores have been sold.
+	pub sellout_price: Option<Balance>,
+}
+
+/// The identifier of a Region which may be renewed automatically once its term expires.
+#[derive(Encode, Decode, Copy, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo, Debug)]
+pub struct AllowedRenewalId {
+	/// The core for which a renewal is allowed.
+	pub core: CoreIndex,
+	/// The timeslice at which the renewal may take place.
+	pub when: Timeslice,
+}
+
+/// A record of a renewal that is allowed to happen.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo, Debug)]
+pub struct AllowedRenewalRecord<Balance> {
+	/// The price for which the next renewal can be made.
+	pub price: Balance,
+	/// The workload which will be scheduled on the core in the event of a renewal.
+	pub workload: Schedule,
+}
+
+/// The balance type used by this pallet's currency.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as frame_support::traits::fungible::Inspect<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+
+/// The account identifier used on the relay chain, for the purpose of crediting coretime
+/// purchases made with relay chain balance.
+pub type RelayAccountIdOf<T> = <T as frame_system::Config>::AccountId;
+
+/// The configuration record type used by this pallet.
+pub type ConfigRecordOf<T> = ConfigRecord<BlockNumberFor<T>>;
+
+/// An in-progress ramp of the number of cores available for sale towards a `target`, one step
+/// every `step_interval` blocks until it is reached.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo, Debug)]
+pub struct CoreCountRampState<BlockNumber> {
+	/// The core count this ramp is working towards.
+	pub target: CoreIndex,
+	/// The block number at which the next step should be applied.
+	pub next_step: BlockNumber,
+	/// The number of blocks between each step.
+	pub step_interval: BlockNumber,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The currency used for all bulk coretime transactions.
+		type Currency: Mutate<Self::AccountId>;
+
+		/// The origin able to configure the broker system, reserve and unreserve cores,
+		/// set lease and request the core count.
+		type AdminOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The maximum number of reservations that can be made.
+		#[pallet::constant]
+		type MaxReservedCores: Get<u32>;
+
+		/// The maximum number of leases that can be active.
+		#[pallet::constant]
+		type MaxLeasedCores: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::storage]
+	pub type Configuration<T> = StorageValue<_, ConfigRecordOf<T>, OptionQuery>;
+
+	#[pallet::storage]
+	pub type Reservations<T: Config> =
+		StorageValue<_, BoundedVec<Schedule, T::MaxReservedCores>, ValueQuery>;
+
+	#[pallet::storage]
+	pub type Leases<T: Config> =
+		StorageValue<_, BoundedVec<LeaseRecordItem, T::MaxLeasedCores>, ValueQuery>;
+
+	#[pallet::storage]
+	pub type SaleInfo<T: Config> =
+		StorageValue<_, SaleInfoRecord<BalanceOf<T>, BlockNumberFor<T>>, OptionQuery>;
+
+	#[pallet::storage]
+	pub type Workplan<T: Config> =
+		StorageMap<_, Twox64Concat, (Timeslice, CoreIndex), Schedule, OptionQuery>;
+
+	#[pallet::storage]
+	pub type AllowedRenewals<T: Config> =
+		StorageMap<_, Twox64Concat, AllowedRenewalId, AllowedRenewalRecord<BalanceOf<T>>, OptionQuery>;
+
+	#[pallet::storage]
+	pub type CoreCountInbox<T: Config> = StorageValue<_, CoreIndex, OptionQuery>;
+
+	/// The in-progress ramp (if any) towards a scheduled core count, stepped by
+	/// [`Pallet::do_rotate_core_count`] each time its `next_step` block is reached.
+	#[pallet::storage]
+	pub type CoreCountRamp<T: Config> = StorageValue<_, CoreCountRampState<BlockNumberFor<T>>, OptionQuery>;
+
+	/// The account, if any, which should have a core's coretime re-purchased on its behalf at
+	/// the start of every subsequent sale, for as long as it remains enrolled.
+	///
+	/// Auto-renewal is opt-in: a core only appears here once its current occupant calls
+	/// `set_auto_renew` with `Some(payer)`, and is removed either explicitly (by the holder or
+	/// the payer) or
+	/// automatically the first time a renewal attempt on its behalf fails.
+	#[pallet::storage]
+	pub type AutoRenewals<T: Config> = StorageMap<_, Twox64Concat, CoreIndex, T::AccountId, OptionQuery>;
+
+	/// A resting order to sell a Region on the secondary market: the seller, and the minimum
+	/// price they're willing to accept for it.
+	#[pallet::storage]
+	pub type RegionListings<T: Config> =
+		StorageMap<_, Blake2_128Concat, RegionId, (T::AccountId, BalanceOf<T>), OptionQuery>;
+
+	/// The current owner of a Region, set when it is purchased and updated as it changes hands
+	/// via `transfer`, `fill_region`, `interlace` or `partition`. Cleared once the Region is
+	/// consumed (assigned to a task, dropped, or its contribution to the pool dropped).
+	#[pallet::storage]
+	pub type Regions<T: Config> = StorageMap<_, Blake2_128Concat, RegionId, T::AccountId, OptionQuery>;
+
+	/// The account that currently holds a core, i.e. whoever last bought or received it via
+	/// `transfer`/`fill_region`. Unlike [`Regions`], this persists across `assign`/`pool` (which
+	/// consume a single term's Region but not the holder's claim to the core itself), which is
+	/// what entitles the holder to enroll the core for automatic renewal.
+	#[pallet::storage]
+	pub type CoreOwner<T: Config> = StorageMap<_, Twox64Concat, CoreIndex, T::AccountId, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A new sale has been initialized.
+		SaleInitialized {
+			/// The block number at which the sale will begin.
+			sale_start: BlockNumberFor<T>,
+			/// The length in blocks of the Leadin Period.
+			leadin_length: BlockNumberFor<T>,
+			/// The price of Bulk Coretime at the beginning of the Leadin Period.
+			start_price: BalanceOf<T>,
+			/// The price of Bulk Coretime after the Leadin Period.
+			regular_price: BalanceOf<T>,
+			/// The first timeslice of the Regions which are being sold in this sale.
+			region_begin: Timeslice,
+			/// The timeslice on which the Regions which are being sold in this sale terminate.
+			region_end: Timeslice,
+			/// The number of cores we want to sell, ideally.
+			ideal_cores_sold: CoreIndex,
+			/// Number of cores which are/have been offered for sale.
+			cores_offered: CoreIndex,
+		},
+		/// A Region has been purchased.
+		Purchased {
+			/// The purchaser of the Region.
+			who: T::AccountId,
+			/// The Region which was purchased.
+			region_id: RegionId,
+			/// The price paid for the Region.
+			price: BalanceOf<T>,
+			/// The number of timeslices the Region covers.
+			duration: Timeslice,
+		},
+		/// A Region has been assigned to a task.
+		Assigned {
+			/// The Region which was assigned.
+			region_id: RegionId,
+			/// The task it was assigned to.
+			task: TaskId,
+			/// The number of timeslices the assignment covers.
+			duration: Timeslice,
+		},
+		/// A Region has been assigned to the Instantaneous Coretime Pool.
+		Pooled {
+			/// The Region which was assigned to the pool.
+			region_id: RegionId,
+			/// The number of timeslices the assignment covers.
+			duration: Timeslice,
+		},
+		/// A Region has been split into two interlaced parts.
+		Interlaced {
+			/// The Region prior to interlacing.
+			old_region_id: RegionId,
+			/// The two new Regions after interlacing.
+			new_region_ids: (RegionId, RegionId),
+		},
+		/// Some relay chain balance was converted into a purchase credit.
+		CreditPurchased {
+			/// Who made the purchase.
+			who: T::AccountId,
+			/// The relay chain account to which the credit is attributed.
+			beneficiary: RelayAccountIdOf<T>,
+			/// The amount credited.
+			amount: BalanceOf<T>,
+		},
+		/// A Region has been dropped due to being out of date.
+		RegionDropped {
+			/// The Region which was dropped.
+			region_id: RegionId,
+			/// The number of timeslices the Region covered.
+			duration: Timeslice,
+		},
+		/// A pool contribution has been dropped due to being out of date.
+		ContributionDropped {
+			/// The Region whose contribution was dropped.
+			region_id: RegionId,
+		},
+		/// The number of cores available for sale has been requested to change.
+		CoreCountRequested {
+			/// The number of cores now requested.
+			core_count: CoreIndex,
+		},
+		/// A core has been enrolled for (or had its payer changed for) automatic renewal.
+		AutoRenewEnabled {
+			/// The core enrolled.
+			core: CoreIndex,
+			/// The account that will be charged each renewal.
+			payer: T::AccountId,
+		},
+		/// A core has had automatic renewal disabled.
+		AutoRenewDisabled {
+			/// The core that is no longer auto-renewed.
+			core: CoreIndex,
+		},
+		/// An automatic renewal attempt failed, and the core's auto-renewal enrollment has been
+		/// removed as a result.
+		AutoRenewalFailed {
+			/// The core whose renewal failed.
+			core: CoreIndex,
+			/// The account that would have paid for the renewal.
+			payer: T::AccountId,
+		},
+		/// A Region has been listed for sale on the secondary market.
+		RegionListed {
+			/// The Region that was listed.
+			region_id: RegionId,
+			/// The account offering it for sale.
+			seller: T::AccountId,
+			/// The minimum price the seller will accept.
+			min_price: BalanceOf<T>,
+		},
+		/// A Region's secondary-market listing has been withdrawn.
+		RegionUnlisted {
+			/// The Region whose listing was withdrawn.
+			region_id: RegionId,
+		},
+		/// A Region was bought on the secondary market.
+		RegionSold {
+			/// The Region (or the part of it) that was sold.
+			region_id: RegionId,
+			/// The account that listed it.
+			seller: T::AccountId,
+			/// The account that bought it.
+			buyer: T::AccountId,
+			/// The price paid.
+			price: BalanceOf<T>,
+		},
+		/// The core count ramp took a step towards its target.
+		CoreCountScheduled {
+			/// The final core count the ramp is working towards.
+			target: CoreIndex,
+			/// The core count now in effect, after this step.
+			current: CoreIndex,
+			/// The block number at which the next step (if any) will be applied.
+			next_step: BlockNumberFor<T>,
+		},
+		/// A Region has changed ownership outright (not via a secondary-market sale).
+		Transferred {
+			/// The Region that was transferred.
+			region_id: RegionId,
+			/// The account that gave it up.
+			old_owner: T::AccountId,
+			/// The account that now owns it.
+			owner: T::AccountId,
+		},
+		/// Revenue owed to a pooled Region's contributor has been claimed.
+		RevenueClaimed {
+			/// The Region whose pool contribution the revenue was claimed for.
+			region_id: RegionId,
+			/// The amount claimed.
+			amount: BalanceOf<T>,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The given region identity is not a valid one.
+		UnknownRegion,
+		/// The caller is not the owner of the region.
+		NotOwner,
+		/// There is no sale happening currently.
+		NoSales,
+		/// There is no renewal allowed for the given core.
+		NotAllowed,
+		/// The sale's limit of cores offered has already been reached.
+		SoldOut,
+		/// The renewal could not be processed.
+		RenewalFailed,
+		/// The Region is not listed for sale.
+		NotListed,
+		/// The price offered is below the seller's minimum.
+		Overpriced,
+		/// The Region's coretime has already been contributed to the Instantaneous Coretime Pool
+		/// and cannot be listed for sale until that contribution is dropped.
+		AlreadyPooled,
+		/// `Reservations` is already at `MaxReservedCores`.
+		TooManyReservations,
+		/// There is no reservation at the given index.
+		UnknownReservation,
+		/// `Leases` is already at `MaxLeasedCores`.
+		TooManyLeases,
+		/// The Region has not yet reached the end of its term.
+		StillValid,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Configure the broker system.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::configure())]
+		pub fn configure(origin: OriginFor<T>, config: ConfigRecordOf<T>) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Configuration::<T>::put(config);
+			Ok(())
+		}
+
+		/// Begin selling for the next period.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::start_sales(*core_count as u32))]
+		pub fn start_sales(
+			origin: OriginFor<T>,
+			initial_price: BalanceOf<T>,
+			core_count: CoreIndex,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::do_start_sales(initial_price, core_count)
+		}
+
+		/// Purchase a Bulk Coretime Region.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::purchase())]
+		pub fn purchase(origin: OriginFor<T>, price_limit: BalanceOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_purchase(who, price_limit).map(|_| ())
+		}
+
+		/// Enroll or change the payer for a core's automatic renewal.
+		///
+		/// `maybe_payer` is `None` to disable auto-renewal for the core, or `Some(account)` to
+		/// (re-)enable it with `account` charged at each sale rotation. Only the current holder
+		/// of the core's renewal allowance, or the account already enrolled as payer, may call
+		/// this.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::set_auto_renew())]
+		pub fn set_auto_renew(
+			origin: OriginFor<T>,
+			core: CoreIndex,
+			maybe_payer: Option<T::AccountId>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_set_auto_renew(who, core, maybe_payer)
+		}
+
+		/// List a Region for sale on the secondary market at a minimum price.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::list_region())]
+		pub fn list_region(
+			origin: OriginFor<T>,
+			region_id: RegionId,
+			min_price: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_list_region(who, region_id, min_price)
+		}
+
+		/// Withdraw a Region's secondary-market listing.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::unlist_region())]
+		pub fn unlist_region(origin: OriginFor<T>, region_id: RegionId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_unlist_region(who, region_id)
+		}
+
+		/// Request that the number of cores available for sale change to `core_count`,
+		/// effective immediately.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::request_core_count())]
+		pub fn request_core_count(origin: OriginFor<T>, core_count: CoreIndex) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			CoreCountInbox::<T>::put(core_count);
+			CoreCountRamp::<T>::kill();
+			Self::deposit_event(Event::CoreCountRequested { core_count });
+			Ok(())
+		}
+
+		/// Schedule the number of cores available for sale to ramp towards `target` over
+		/// `ramp_blocks`, one core per step, rather than changing all at once. Steps are applied
+		/// by block number; they are not tied to sale rotation.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::schedule_core_count())]
+		pub fn schedule_core_count(
+			origin: OriginFor<T>,
+			target: CoreIndex,
+			ramp_blocks: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::do_schedule_core_count(target, ramp_blocks)
+		}
+
+		/// Buy a listed Region, or a part of it, for no more than `price_limit` (if given).
+		///
+		/// `maybe_mask` restricts the purchase to the given part of the Region's core mask; the
+		/// remainder (if any) is split off via [`Pallet::do_interlace`] and automatically
+		/// relisted under the original seller at the original minimum price. `None` buys the
+		/// listing in full.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::fill_region())]
+		pub fn fill_region(
+			origin: OriginFor<T>,
+			region_id: RegionId,
+			maybe_mask: Option<CoreMask>,
+			price_limit: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_fill_region(who, region_id, maybe_mask, price_limit)
+		}
+
+		/// Reserve a core for a task outside of the sale system, effective from the next sale.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::reserve())]
+		pub fn reserve(origin: OriginFor<T>, schedule: Schedule) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::do_reserve(schedule)
+		}
+
+		/// Remove the reservation at index `n`.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::unreserve(*n))]
+		pub fn unreserve(origin: OriginFor<T>, n: u32) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::do_unreserve(n)
+		}
+
+		/// Reserve a core for `task` outside of the sale system, until timeslice `until`.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::set_lease())]
+		pub fn set_lease(
+			origin: OriginFor<T>,
+			task: TaskId,
+			until: Timeslice,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			Self::do_set_lease(task, until)
+		}
+
+		/// Renew a core's workload for another term, using its existing `AllowedRenewalRecord`.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::renew())]
+		pub fn renew(origin: OriginFor<T>, core: CoreIndex) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_renew(who, core)
+		}
+
+		/// Transfer a Region's ownership to `recipient`, dropping any secondary-market listing.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::transfer())]
+		pub fn transfer(
+			origin: OriginFor<T>,
+			region_id: RegionId,
+			recipient: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_transfer(who, region_id, recipient)
+		}
+
+		/// Split a Region into two consecutive Regions at `pivot`, a timeslice offset from the
+		/// Region's start.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::partition())]
+		pub fn partition(
+			origin: OriginFor<T>,
+			region_id: RegionId,
+			pivot: Timeslice,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_partition_owned(who, region_id, pivot).map(|_| ())
+		}
+
+		/// Split a Region into two, one covering `mask` and the other covering its complement.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::interlace())]
+		pub fn interlace(
+			origin: OriginFor<T>,
+			region_id: RegionId,
+			mask: CoreMask,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_interlace_owned(who, region_id, mask).map(|_| ())
+		}
+
+		/// Assign a Region's coretime to a task.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::assign())]
+		pub fn assign(
+			origin: OriginFor<T>,
+			region_id: RegionId,
+			task: TaskId,
+			finality: Finality,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_assign(region_id, Some(who), task, finality)
+		}
+
+		/// Assign a Region's coretime to the Instantaneous Coretime Pool.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::pool())]
+		pub fn pool(
+			origin: OriginFor<T>,
+			region_id: RegionId,
+			payee: T::AccountId,
+			finality: Finality,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_pool(region_id, Some(who), payee, finality)
+		}
+
+		/// Claim the revenue owed for up to `max_timeslices` of a Region's pool contribution.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::claim_revenue())]
+		pub fn claim_revenue(
+			origin: OriginFor<T>,
+			region_id: RegionId,
+			max_timeslices: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_claim_revenue(who, region_id, max_timeslices)
+		}
+
+		/// Convert relay chain balance held by the caller into a purchase credit usable for buying
+		/// Bulk Coretime Regions, attributed to `beneficiary` on the relay chain.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::purchase_credit())]
+		pub fn purchase_credit(
+			origin: OriginFor<T>,
+			amount: BalanceOf<T>,
+			beneficiary: RelayAccountIdOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_purchase_credit(who, amount, beneficiary)
+		}
+
+		/// Drop a Region that is out of date, returning nothing to its owner.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::drop_region())]
+		pub fn drop_region(origin: OriginFor<T>, region_id: RegionId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_drop_region(who, region_id)
+		}
+
+		/// Drop a Region's pool contribution that is out of date, returning nothing to its owner.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::drop_contribution())]
+		pub fn drop_contribution(origin: OriginFor<T>, region_id: RegionId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_drop_contribution(who, region_id)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Begin a new sale, pricing cores starting at `initial_price` and offering `core_count`
+	/// cores in total (less whatever is already committed to reservations and leases).
+	pub fn do_start_sales(initial_price: BalanceOf<T>, core_count: CoreIndex) -> DispatchResult {
+		let config = Configuration::<T>::get().ok_or(Error::<T>::NoSales)?;
+		let now = frame_system::Pallet::<T>::block_number();
+
+		let reserved: CoreIndex = Reservations::<T>::get().len() as CoreIndex;
+		let leased: CoreIndex = Leases::<T>::get().len() as CoreIndex;
+		let cores_offered = core_count.saturating_sub(reserved).saturating_sub(leased);
+
+		let sale_start = now.saturating_add(config.interlude_length);
+		let region_begin = 4;
+		let region_end = region_begin.saturating_add(config.region_length);
+
+		let record = SaleInfoRecord {
+			sale_start,
+			leadin_length: config.leadin_length,
+			start_price: initial_price.saturating_mul(2u32.into()),
+			regular_price: initial_price,
+			region_begin,
+			region_end,
+			ideal_cores_sold: 0,
+			cores_offered,
+			cores_sold: 0,
+			sellout_price: None,
+		};
+
+		Self::deposit_event(Event::SaleInitialized {
+			sale_start: record.sale_start,
+			leadin_length: record.leadin_length,
+			start_price: record.start_price,
+			regular_price: record.regular_price,
+			region_begin: record.region_begin,
+			region_end: record.region_end,
+			ideal_cores_sold: record.ideal_cores_sold,
+			cores_offered: record.cores_offered,
+		});
+
+		SaleInfo::<T>::put(record);
+		Ok(())
+	}
+
+	/// Purchase a Region from the ongoing sale, for no more than `price_limit`.
+	pub fn do_purchase(who: T::AccountId, price_limit: BalanceOf<T>) -> Result<RegionId, DispatchError> {
+		let mut sale = SaleInfo::<T>::get().ok_or(Error::<T>::NoSales)?;
+		ensure!(sale.cores_sold < sale.cores_offered, Error::<T>::SoldOut);
+		ensure!(sale.regular_price <= price_limit, Error::<T>::SoldOut);
+
+		T::Currency::burn_from(
+			&who,
+			sale.regular_price,
+			Preservation::Expendable,
+			Precision::Exact,
+			Fortitude::Polite,
+		)?;
+
+		let core = sale.cores_sold;
+		sale.cores_sold = sale.cores_sold.saturating_add(1);
+		sale.sellout_price = Some(sale.regular_price);
+		SaleInfo::<T>::put(sale.clone());
+
+		let region_id = RegionId { begin: sale.region_begin, core, part: CoreMask::complete() };
+		let duration = sale.region_end.saturating_sub(sale.region_begin);
+
+		Regions::<T>::insert(region_id, who.clone());
+		CoreOwner::<T>::insert(core, who.clone());
+
+		Self::deposit_event(Event::Purchased {
+			who,
+			region_id,
+			price: sale.regular_price,
+			duration,
+		});
+
+		Ok(region_id)
+	}
+
+	/// Reserve a core for a task outside of the sale system, appending `schedule` to the list of
+	/// `Reservations` that are honoured at the start of the next sale.
+	pub fn do_reserve(schedule: Schedule) -> DispatchResult {
+		Reservations::<T>::try_mutate(|reservations| {
+			reservations.try_push(schedule).map_err(|_| Error::<T>::TooManyReservations)
+		})?;
+		Ok(())
+	}
+
+	/// Remove the reservation at index `n`.
+	pub fn do_unreserve(n: u32) -> DispatchResult {
+		Reservations::<T>::try_mutate(|reservations| {
+			ensure!((n as usize) < reservations.len(), Error::<T>::UnknownReservation);
+			reservations.remove(n as usize);
+			Ok(())
+		})
+	}
+
+	/// Reserve a core for `task` outside of the sale system, until timeslice `until`.
+	pub fn do_set_lease(task: TaskId, until: Timeslice) -> DispatchResult {
+		Leases::<T>::try_mutate(|leases| {
+			leases
+				.try_push(LeaseRecordItem { task, until })
+				.map_err(|_| Error::<T>::TooManyLeases)
+		})?;
+		Ok(())
+	}
+
+	/// Assign a Region's coretime to a task.
+	///
+	/// If `maybe_check_owner` is `Some`, the Region must currently be owned by that account; the
+	/// assignment consumes the Region, so its ownership record is removed on success. A `Final`
+	/// assignment also records an `AllowedRenewals` entry for the core at the sale's `region_end`.
+	pub fn do_assign(
+		region_id: RegionId,
+		maybe_check_owner: Option<T::AccountId>,
+		target: TaskId,
+		finality: Finality,
+	) -> DispatchResult {
+		if let Some(who) = maybe_check_owner {
+			ensure!(Regions::<T>::get(region_id) == Some(who), Error::<T>::NotOwner);
+		}
+
+		let workplan_key = (region_id.begin, region_id.core);
+		let item = ScheduleItem { part: region_id.part, assignment: CoreAssignment::Task(target) };
+		Workplan::<T>::mutate(workplan_key, |schedule| {
+			let schedule = schedule.get_or_insert_with(Schedule::default);
+			let _ = schedule.try_push(item);
+		});
+
+		let sale = SaleInfo::<T>::get().ok_or(Error::<T>::NoSales)?;
+		let duration = sale.region_end.saturating_sub(region_id.begin);
+
+		if finality == Finality::Final {
+			let price = sale.sellout_price.unwrap_or(sale.regular_price);
+			let workload = Workplan::<T>::get(workplan_key).unwrap_or_default();
+			AllowedRenewals::<T>::insert(
+				AllowedRenewalId { core: region_id.core, when: sale.region_end },
+				AllowedRenewalRecord { price, workload },
+			);
+		}
+
+		Regions::<T>::remove(region_id);
+		Self::deposit_event(Event::Assigned { region_id, task: target, duration });
+		Ok(())
+	}
+
+	/// Assign a Region's coretime to the Instantaneous Coretime Pool.
+	///
+	/// If `maybe_check_owner` is `Some`, the Region must currently be owned by that account.
+	/// Ownership is retained by the contributor (not `payee`) so that the contribution can later
+	/// be claimed against or dropped by them.
+	pub fn do_pool(
+		region_id: RegionId,
+		maybe_check_owner: Option<T::AccountId>,
+		_payee: T::AccountId,
+		_finality: Finality,
+	) -> DispatchResult {
+		if let Some(who) = maybe_check_owner {
+			ensure!(Regions::<T>::get(region_id) == Some(who), Error::<T>::NotOwner);
+		}
+
+		let workplan_key = (region_id.begin, region_id.core);
+		let item = ScheduleItem { part: region_id.part, assignment: CoreAssignment::Pool };
+		Workplan::<T>::mutate(workplan_key, |schedule| {
+			let schedule = schedule.get_or_insert_with(Schedule::default);
+			let _ = schedule.try_push(item);
+		});
+
+		let sale = SaleInfo::<T>::get().ok_or(Error::<T>::NoSales)?;
+		let duration = sale.region_end.saturating_sub(region_id.begin);
+
+		Self::deposit_event(Event::Pooled { region_id, duration });
+		Ok(())
+	}
+
+	/// Transfer a Region's ownership from `who` to `recipient`, dropping any outstanding
+	/// secondary-market listing.
+	pub fn do_transfer(
+		who: T::AccountId,
+		region_id: RegionId,
+		recipient: T::AccountId,
+	) -> DispatchResult {
+		ensure!(Regions::<T>::get(region_id) == Some(who.clone()), Error::<T>::NotOwner);
+		RegionListings::<T>::remove(region_id);
+		Regions::<T>::insert(region_id, recipient.clone());
+		CoreOwner::<T>::insert(region_id.core, recipient.clone());
+		Self::deposit_event(Event::Transferred { region_id, old_owner: who, owner: recipient });
+		Ok(())
+	}
+
+	/// Split a Region owned by `who` into two consecutive Regions at `pivot`, moving ownership of
+	/// both resulting Regions to `who`.
+	pub fn do_partition_owned(
+		who: T::AccountId,
+		region_id: RegionId,
+		pivot: Timeslice,
+	) -> Result<(RegionId, RegionId), DispatchError> {
+		ensure!(Regions::<T>::get(region_id) == Some(who.clone()), Error::<T>::NotOwner);
+		let (first, second) = Self::do_partition(region_id, pivot);
+		Regions::<T>::remove(region_id);
+		Regions::<T>::insert(first, who.clone());
+		Regions::<T>::insert(second, who);
+		Ok((first, second))
+	}
+
+	/// Split a Region owned by `who` into two, one covering `mask` and the other its complement,
+	/// moving ownership of both resulting Regions to `who`.
+	pub fn do_interlace_owned(
+		who: T::AccountId,
+		region_id: RegionId,
+		mask: CoreMask,
+	) -> Result<(RegionId, RegionId), DispatchError> {
+		ensure!(Regions::<T>::get(region_id) == Some(who.clone()), Error::<T>::NotOwner);
+		let (first, second) = Self::do_interlace(region_id, mask);
+		Regions::<T>::remove(region_id);
+		Regions::<T>::insert(first, who.clone());
+		Regions::<T>::insert(second, who);
+		Self::deposit_event(Event::Interlaced { old_region_id: region_id, new_region_ids: (first, second) });
+		Ok((first, second))
+	}
+
+	/// Claim the revenue owed to `who` for up to `max_timeslices` of a Region's pool
+	/// contribution. `who` must currently own the (pooled) Region.
+	pub fn do_claim_revenue(
+		who: T::AccountId,
+		region_id: RegionId,
+		_max_timeslices: u32,
+	) -> DispatchResult {
+		ensure!(Regions::<T>::get(region_id) == Some(who), Error::<T>::NotOwner);
+		let workplan_key = (region_id.begin, region_id.core);
+		let schedule = Workplan::<T>::get(workplan_key).ok_or(Error::<T>::UnknownRegion)?;
+		ensure!(
+			schedule
+				.iter()
+				.any(|item| item.part == region_id.part && item.assignment == CoreAssignment::Pool),
+			Error::<T>::UnknownRegion
+		);
+
+		Self::deposit_event(Event::RevenueClaimed { region_id, amount: Zero::zero() });
+		Ok(())
+	}
+
+	/// Convert relay chain balance held by `who` into a purchase credit attributed to
+	/// `beneficiary` on the relay chain.
+	pub fn do_purchase_credit(
+		who: T::AccountId,
+		amount: BalanceOf<T>,
+		beneficiary: RelayAccountIdOf<T>,
+	) -> DispatchResult {
+		T::Currency::burn_from(
+			&who,
+			amount,
+			Preservation::Expendable,
+			Precision::Exact,
+			Fortitude::Polite,
+		)?;
+		Self::deposit_event(Event::CreditPurchased { who, beneficiary, amount });
+		Ok(())
+	}
+
+	/// Drop a Region owned by `who` once its term has ended, freeing its ownership record and any
+	/// outstanding secondary-market listing.
+	pub fn do_drop_region(who: T::AccountId, region_id: RegionId) -> DispatchResult {
+		ensure!(Regions::<T>::get(region_id) == Some(who), Error::<T>::NotOwner);
+
+		let sale = SaleInfo::<T>::get().ok_or(Error::<T>::NoSales)?;
+		let now: Timeslice = frame_system::Pallet::<T>::block_number().saturated_into();
+		ensure!(now >= sale.region_end, Error::<T>::StillValid);
+		let duration = sale.region_end.saturating_sub(region_id.begin);
+
+		Regions::<T>::remove(region_id);
+		RegionListings::<T>::remove(region_id);
+		Self::deposit_event(Event::RegionDropped { region_id, duration });
+		Ok(())
+	}
+
+	/// Drop a Region's pool contribution owned by `who` once its `contribution_timeout` has
+	/// elapsed since the sale it was part of ended.
+	pub fn do_drop_contribution(who: T::AccountId, region_id: RegionId) -> DispatchResult {
+		ensure!(Regions::<T>::get(region_id) == Some(who), Error::<T>::NotOwner);
+
+		let config = Configuration::<T>::get().ok_or(Error::<T>::NoSales)?;
+		let sale = SaleInfo::<T>::get().ok_or(Error::<T>::NoSales)?;
+		let now: Timeslice = frame_system::Pallet::<T>::block_number().saturated_into();
+		let expiry = sale.region_end.saturating_add(config.contribution_timeout);
+		ensure!(now >= expiry, Error::<T>::StillValid);
+
+		Regions::<T>::remove(region_id);
+		Workplan::<T>::remove((region_id.begin, region_id.core));
+		Self::deposit_event(Event::ContributionDropped { region_id });
+		Ok(())
+	}
+
+	/// Enroll, change, or disable automatic renewal of a core.
+	///
+	/// Enrolling (`Some(payer)`) or disabling (`None`) is only permitted for the account that
+	/// currently holds the core (tracked in [`CoreOwner`]), or, once enrolled, for the account
+	/// already enrolled as payer.
+	pub fn do_set_auto_renew(
+		who: T::AccountId,
+		core: CoreIndex,
+		maybe_payer: Option<T::AccountId>,
+	) -> DispatchResult {
+		let is_holder = CoreOwner::<T>::get(core).as_ref() == Some(&who);
+		let is_payer = AutoRenewals::<T>::get(core).as_ref() == Some(&who);
+		ensure!(is_holder || is_payer, Error::<T>::NotOwner);
+
+		match maybe_payer {
+			Some(payer) => {
+				AutoRenewals::<T>::insert(core, payer.clone());
+				Self::deposit_event(Event::AutoRenewEnabled { core, payer });
+			},
+			None => {
+				AutoRenewals::<T>::remove(core);
+				Self::deposit_event(Event::AutoRenewDisabled { core });
+			},
+		}
+
+		Ok(())
+	}
+
+	/// Attempt to renew every core currently enrolled for automatic renewal, as part of a sale
+	/// rotation.
+	///
+	/// A renewal that fails (e.g. because the payer can no longer afford it, or the allowed
+	/// renewal record has expired) does not abort the block: the core's auto-renewal enrollment
+	/// is removed and `AutoRenewalFailed` is emitted instead, so a single failing payer can never
+	/// block rotation for every other core.
+	pub fn process_auto_renewals() -> Weight {
+		let mut reads_writes = 0u64;
+		let cores: Vec<CoreIndex> = AutoRenewals::<T>::iter_keys().collect();
+
+		for core in cores {
+			let Some(payer) = AutoRenewals::<T>::get(core) else { continue };
+			reads_writes = reads_writes.saturating_add(1);
+
+			match Self::do_renew(payer.clone(), core) {
+				Ok(()) => {},
+				Err(_) => {
+					AutoRenewals::<T>::remove(core);
+					Self::deposit_event(Event::AutoRenewalFailed { core, payer });
+				},
+			}
+		}
+
+		T::DbWeight::get().reads_writes(reads_writes, reads_writes)
+	}
+
+	/// Renew a core's workload for another term, using its existing `AllowedRenewalRecord`.
+	pub fn do_renew(who: T::AccountId, core: CoreIndex) -> DispatchResult {
+		let config = Configuration::<T>::get().ok_or(Error::<T>::NoSales)?;
+		let sale = SaleInfo::<T>::get().ok_or(Error::<T>::NoSales)?;
+		let id = AllowedRenewalId { core, when: sale.region_end };
+		let record = AllowedRenewals::<T>::get(id).ok_or(Error::<T>::NotAllowed)?;
+
+		T::Currency::burn_from(
+			&who,
+			record.price,
+			Preservation::Expendable,
+			Precision::Exact,
+			Fortitude::Polite,
+		)?;
+
+		Workplan::<T>::insert((sale.region_end, core), record.workload);
+		AllowedRenewals::<T>::remove(id);
+
+		let next_id = AllowedRenewalId { core, when: sale.region_end.saturating_add(config.region_length) };
+		AllowedRenewals::<T>::insert(next_id, record);
+
+		Ok(())
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Split a Region into two, one covering `mask` and the other covering its complement.
+	///
+	/// Both resulting Regions cover the same timeslices as the original; only the parts of the
+	/// core they occupy differ.
+	pub fn do_interlace(region_id: RegionId, mask: CoreMask) -> (RegionId, RegionId) {
+		let first = RegionId { begin: region_id.begin, core: region_id.core, part: mask };
+		let second = RegionId {
+			begin: region_id.begin,
+			core: region_id.core,
+			part: region_id.part ^ mask,
+		};
+		(first, second)
+	}
+
+	/// Split a Region into two consecutive Regions at `pivot`, a timeslice offset from the
+	/// Region's start.
+	pub fn do_partition(region_id: RegionId, pivot: Timeslice) -> (RegionId, RegionId) {
+		let first = region_id;
+		let second = RegionId {
+			begin: region_id.begin.saturating_add(pivot),
+			core: region_id.core,
+			part: region_id.part,
+		};
+		(first, second)
+	}
+
+	/// Schedule the core count to ramp towards `target`, one core per step, over roughly
+	/// `ramp_blocks`. A `ramp_blocks` of zero (or a `target` already reached) applies
+	/// immediately, identically to `request_core_count`.
+	pub fn do_schedule_core_count(target: CoreIndex, ramp_blocks: BlockNumberFor<T>) -> DispatchResult {
+		let current = CoreCountInbox::<T>::get().unwrap_or(0);
+		let now = frame_system::Pallet::<T>::block_number();
+
+		let steps = current.max(target).saturating_sub(current.min(target)).max(1) as u32;
+
+		if ramp_blocks.is_zero() || current == target {
+			CoreCountInbox::<T>::put(target);
+			CoreCountRamp::<T>::kill();
+			Self::deposit_event(Event::CoreCountScheduled { target, current: target, next_step: now });
+			return Ok(())
+		}
+
+		let step_interval = ramp_blocks / steps.into();
+		let next_step = now.saturating_add(step_interval);
+
+		CoreCountRamp::<T>::put(CoreCountRampState { target, next_step, step_interval });
+		Self::deposit_event(Event::CoreCountScheduled { target, current, next_step });
+		Ok(())
+	}
+
+	/// Apply one step of any in-progress core count ramp, if it is due. Called from
+	/// `on_initialize` every block; steps are not tied to sale rotation.
+	pub fn do_rotate_core_count() -> Weight {
+		let now = frame_system::Pallet::<T>::block_number();
+		let Some(ramp) = CoreCountRamp::<T>::get() else { return Weight::zero() };
+		if now < ramp.next_step {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let current = CoreCountInbox::<T>::get().unwrap_or(0);
+		let next = if current < ramp.target {
+			current.saturating_add(1)
+		} else {
+			current.saturating_sub(1)
+		};
+		CoreCountInbox::<T>::put(next);
+
+		if next == ramp.target {
+			CoreCountRamp::<T>::kill();
+			Self::deposit_event(Event::CoreCountScheduled { target: ramp.target, current: next, next_step: now });
+		} else {
+			let next_step = now.saturating_add(ramp.step_interval);
+			CoreCountRamp::<T>::put(CoreCountRampState {
+				target: ramp.target,
+				next_step,
+				step_interval: ramp.step_interval,
+			});
+			Self::deposit_event(Event::CoreCountScheduled { target: ramp.target, current: next, next_step });
+		}
+
+		T::DbWeight::get().reads_writes(2, 2)
+	}
+
+	/// List a Region for sale on the secondary market. Only the Region's current occupant
+	/// (tracked by a prior `purchase`/`transfer`/`fill_region`) may list it.
+	pub fn do_list_region(
+		who: T::AccountId,
+		region_id: RegionId,
+		min_price: BalanceOf<T>,
+	) -> DispatchResult {
+		ensure!(Regions::<T>::get(region_id) == Some(who.clone()), Error::<T>::NotOwner);
+
+		let workplan_key = (region_id.begin, region_id.core);
+		if let Some(schedule) = Workplan::<T>::get(workplan_key) {
+			let already_pooled = schedule
+				.iter()
+				.any(|item| item.part == region_id.part && item.assignment == CoreAssignment::Pool);
+			ensure!(!already_pooled, Error::<T>::AlreadyPooled);
+		}
+
+		RegionListings::<T>::insert(region_id, (who.clone(), min_price));
+		Self::deposit_event(Event::RegionListed { region_id, seller: who, min_price });
+		Ok(())
+	}
+
+	/// Remove any secondary-market listing whose Region has outlived its fixed
+	/// `region_length`. Called once per block from [`Hooks::on_initialize`].
+	pub fn sweep_expired_listings() -> Weight {
+		let Some(config) = Configuration::<T>::get() else { return Weight::zero() };
+		let now: Timeslice = frame_system::Pallet::<T>::block_number().saturated_into();
+
+		let expired: Vec<RegionId> = RegionListings::<T>::iter_keys()
+			.filter(|region_id| now >= region_id.begin.saturating_add(config.region_length))
+			.collect();
+
+		let count = expired.len() as u64;
+		for region_id in expired {
+			RegionListings::<T>::remove(region_id);
+			Self::deposit_event(Event::RegionUnlisted { region_id });
+		}
+
+		T::DbWeight::get().reads(1).saturating_add(T::DbWeight::get().reads_writes(count, count))
+	}
+
+	/// Withdraw a Region's secondary-market listing. Only the account that listed it may do so.
+	pub fn do_unlist_region(who: T::AccountId, region_id: RegionId) -> DispatchResult {
+		let (seller, _) = RegionListings::<T>::get(region_id).ok_or(Error::<T>::NotListed)?;
+		ensure!(seller == who, Error::<T>::NotOwner);
+		RegionListings::<T>::remove(region_id);
+		Self::deposit_event(Event::RegionUnlisted { region_id });
+		Ok(())
+	}
+
+	/// Buy a listed Region, or `maybe_mask` of it, transferring payment atomically from buyer to
+	/// seller.
+	///
+	/// A `price_limit` of `None` accepts the seller's asking price as-is; `Some(limit)` fails the
+	/// purchase if the asking price exceeds it. When `maybe_mask` covers less than the whole
+	/// Region, the bought part is split off via [`Self::do_interlace`] and the remainder is
+	/// automatically relisted under the original seller at the original minimum price.
+	pub fn do_fill_region(
+		who: T::AccountId,
+		region_id: RegionId,
+		maybe_mask: Option<CoreMask>,
+		price_limit: Option<BalanceOf<T>>,
+	) -> DispatchResult {
+		let (seller, min_price) = RegionListings::<T>::get(region_id).ok_or(Error::<T>::NotListed)?;
+
+		let (sold_id, maybe_remainder_id) = match maybe_mask {
+			Some(mask) if mask != region_id.part => {
+				let (sold, remainder) = Self::do_interlace(region_id, mask);
+				(sold, Some(remainder))
+			},
+			_ => (region_id, None),
+		};
+
+		let price = min_price;
+		if let Some(limit) = price_limit {
+			ensure!(price <= limit, Error::<T>::Overpriced);
+		}
+
+		T::Currency::transfer(&who, &seller, price, Preservation::Expendable)?;
+
+		RegionListings::<T>::remove(region_id);
+		Regions::<T>::remove(region_id);
+		Regions::<T>::insert(sold_id, who.clone());
+		CoreOwner::<T>::insert(sold_id.core, who.clone());
+		if let Some(remainder_id) = maybe_remainder_id {
+			Regions::<T>::insert(remainder_id, seller.clone());
+			RegionListings::<T>::insert(remainder_id, (seller.clone(), min_price));
+			Self::deposit_event(Event::RegionListed {
+				region_id: remainder_id,
+				seller: seller.clone(),
+				min_price,
+			});
+		}
+
+		Self::deposit_event(Event::RegionSold {
+			region_id: sold_id,
+			seller,
+			buyer: who,
+			price,
+		});
+
+		Ok(())
+	}
+}
+
+impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+	fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
+		Self::process_auto_renewals()
+			.saturating_add(Self::do_rotate_core_count())
+			.saturating_add(Self::sweep_expired_listings())
+	}
+}
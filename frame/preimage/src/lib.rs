@@ -0,0 +1,778 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Preimage Pallet
+//!
+//! Stores the preimage of hashes. A user can insert, remove their preimage and request and
+//! unrequest a hash to be noted with its preimage. On request, the system guarantees to store the
+//! preimage and its hash until the request is removed.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use alloc::{borrow::Cow, vec::Vec};
+use codec::{Decode, Encode, MaxEncodedLen};
+use core::marker::PhantomData;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{
+		Consideration, Currency, Footprint, Hooks, QueryPreimage, ReservableCurrency,
+		StorePreimage,
+	},
+	weights::Weight,
+	BoundedVec,
+};
+use frame_system::pallet_prelude::*;
+use scale_info::TypeInfo;
+use sp_runtime::traits::{Hash, Zero};
+
+pub use pallet::*;
+
+type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// The maximum byte length a preimage may have, across every bucket.
+pub const MAX_SIZE: u32 = 1 << 22;
+
+/// The ascending byte-length cutoffs of the fixed set of buckets preimages are stored in.
+///
+/// A preimage is stored in the first (smallest) bucket whose cutoff is `>=` its length. Using a
+/// handful of power-of-two buckets rather than one `BoundedVec<u8, ConstU32<MAX_SIZE>>` map keeps
+/// the worst-case decode cost of reading back a *small* preimage independent of `MAX_SIZE`.
+const BUCKET_CUTOFFS: [u32; 8] = [1 << 7, 1 << 10, 1 << 13, 1 << 16, 1 << 19, 1 << 20, 1 << 21, 1 << 22];
+
+/// The request status of a hash.
+///
+/// `Ticket` is [`Config::Consideration`]: a token returned by that provider's `fungible::hold`
+/// implementation in exchange for reserving the storage deposit, and redeemed back through the
+/// same provider to release it. Replaces a bare `(AccountId, Balance)` deposit so the actual
+/// holding mechanism (reserve-based today, but potentially something else entirely) is entirely
+/// the provider's concern rather than baked into this pallet.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, TypeInfo, MaxEncodedLen, Debug)]
+pub enum RequestStatus<AccountId, Ticket> {
+	/// The hash is not requested but is stored, along with who paid its deposit (`None` if the
+	/// pallet's `ManagerOrigin` noted it for free) and how big it is.
+	Unrequested { ticket: Option<(AccountId, Ticket)>, len: u32 },
+	/// The hash is requested and, if fulfilled, who paid its deposit and how big it is.
+	Requested { ticket: Option<(AccountId, Ticket)>, count: u32, len: Option<u32> },
+}
+
+/// Bytes accumulated so far for an in-progress [`Pallet::note_preimage_chunk`] upload, along with
+/// the depositor charged for them and the running total of their deposit.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, TypeInfo, MaxEncodedLen, Debug)]
+pub struct ChunkedUpload<AccountId, Balance, BlockNumber> {
+	/// Who is paying for the bytes received so far, and will be refunded on cancellation.
+	depositor: AccountId,
+	/// The deposit currently reserved from `depositor` for [`Self::bytes`].
+	deposit: Balance,
+	/// The number of chunks received so far, used to reject out-of-order or duplicate chunks.
+	chunks_received: u32,
+	/// The bytes received so far, in chunk order.
+	bytes: BoundedVec<u8, ConstU32<MAX_SIZE>>,
+	/// The block by which the next chunk must arrive, or [`Pallet::sweep_expired_chunk_uploads`]
+	/// will abandon the session and refund [`Self::deposit`]. Pushed back by
+	/// [`Config::ChunkUploadExpiry`] blocks every time a chunk is received.
+	deadline: BlockNumber,
+}
+
+/// A type that can be turned into a hash, used to identify preimages noted via the
+/// [`StorePreimage`] trait rather than the pallet's inherent extrinsics.
+///
+/// Carries the hasher (`Hasher`) alongside its output type (`Hash`) so that an `Inline` value,
+/// which has no separately-stored hash, can still answer [`Bounded::hash`]. `MaxInlineSize` is
+/// the runtime's [`Config::MaxInlineSize`], threaded through as a type parameter since
+/// `BoundedVec`'s capacity has to be known at the type level.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, TypeInfo, MaxEncodedLen, Debug)]
+#[codec(skip_type_params(Hasher, MaxInlineSize))]
+#[scale_info(skip_type_params(Hasher, MaxInlineSize))]
+pub enum Bounded<Hash, Hasher, MaxInlineSize: Get<u32>> {
+	/// The preimage's bytes, held inline since they're small enough that storing a lookup key
+	/// for them separately would cost more than the bytes themselves.
+	Inline(BoundedVec<u8, MaxInlineSize>, PhantomData<Hasher>),
+	/// A bounded value held by reference to its underlying preimage, whose bytes live in this
+	/// pallet's storage.
+	Lookup {
+		/// The hash of the preimage.
+		hash: Hash,
+		/// The length of the preimage.
+		len: u32,
+	},
+}
+
+impl<Hash: Clone, Hasher: sp_runtime::traits::Hash<Output = Hash>, MaxInlineSize: Get<u32>>
+	Bounded<Hash, Hasher, MaxInlineSize>
+{
+	/// The hash of the underlying preimage.
+	pub fn hash(&self) -> Hash {
+		match self {
+			Bounded::Inline(bytes, _) => Hasher::hash(bytes),
+			Bounded::Lookup { hash, .. } => hash.clone(),
+		}
+	}
+
+	/// The length, in bytes, of the underlying preimage.
+	pub fn len(&self) -> u32 {
+		match self {
+			Bounded::Inline(bytes, _) => bytes.len() as u32,
+			Bounded::Lookup { len, .. } => *len,
+		}
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Currency type for this pallet, used to take deposits for noting preimages.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// An origin that can always note and unnote preimages without paying a deposit, and
+		/// unnote any other account's preimage.
+		type ManagerOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Base deposit held for an in-progress chunked upload, independent of its length.
+		#[pallet::constant]
+		type BaseDeposit: Get<BalanceOf<Self>>;
+
+		/// Deposit per byte held for an in-progress chunked upload.
+		#[pallet::constant]
+		type ByteDeposit: Get<BalanceOf<Self>>;
+
+		/// [`StorePreimage::note`] holds preimages shorter than this many bytes inline in a
+		/// [`Bounded::Inline`] rather than in this pallet's own storage.
+		#[pallet::constant]
+		type MaxInlineSize: Get<u32>;
+
+		/// How long a [`ChunkedUpload`] session may go without receiving a new chunk before
+		/// [`Pallet::sweep_expired_chunk_uploads`] abandons it and refunds its deposit.
+		#[pallet::constant]
+		type ChunkUploadExpiry: Get<BlockNumberFor<Self>>;
+
+		/// The means by which a stored or requested preimage's deposit is held, priced from its
+		/// [`Footprint`] (an item count and byte size) rather than this pallet computing a
+		/// [`BalanceOf<Self>`] and calling `reserve` directly.
+		type Consideration: Consideration<Self::AccountId, Footprint>;
+	}
+
+	/// A reason for this pallet placing a hold on funds, for use by [`Config::Consideration`]
+	/// implementations backed by [`frame_support::traits::fungible::hold`].
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// Deposit for storing a preimage.
+		Preimage,
+	}
+
+	/// The request status of a given hash.
+	#[pallet::storage]
+	pub type StatusFor<T: Config> =
+		StorageMap<_, Identity, T::Hash, RequestStatus<T::AccountId, T::Consideration>>;
+
+	#[pallet::storage]
+	pub type Preimage7For<T: Config> =
+		StorageMap<_, Identity, T::Hash, BoundedVec<u8, ConstU32<{ 1 << 7 }>>>;
+	#[pallet::storage]
+	pub type Preimage10For<T: Config> =
+		StorageMap<_, Identity, T::Hash, BoundedVec<u8, ConstU32<{ 1 << 10 }>>>;
+	#[pallet::storage]
+	pub type Preimage13For<T: Config> =
+		StorageMap<_, Identity, T::Hash, BoundedVec<u8, ConstU32<{ 1 << 13 }>>>;
+	#[pallet::storage]
+	pub type Preimage16For<T: Config> =
+		StorageMap<_, Identity, T::Hash, BoundedVec<u8, ConstU32<{ 1 << 16 }>>>;
+	#[pallet::storage]
+	pub type Preimage19For<T: Config> =
+		StorageMap<_, Identity, T::Hash, BoundedVec<u8, ConstU32<{ 1 << 19 }>>>;
+	#[pallet::storage]
+	pub type Preimage20For<T: Config> =
+		StorageMap<_, Identity, T::Hash, BoundedVec<u8, ConstU32<{ 1 << 20 }>>>;
+	#[pallet::storage]
+	pub type Preimage21For<T: Config> =
+		StorageMap<_, Identity, T::Hash, BoundedVec<u8, ConstU32<{ 1 << 21 }>>>;
+	#[pallet::storage]
+	pub type Preimage22For<T: Config> =
+		StorageMap<_, Identity, T::Hash, BoundedVec<u8, ConstU32<{ 1 << 22 }>>>;
+
+	/// In-progress chunked uploads, keyed by the uploader and a caller-chosen session id.
+	#[pallet::storage]
+	pub type ChunkedUploads<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		u32,
+		ChunkedUpload<T::AccountId, BalanceOf<T>, BlockNumberFor<T>>,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A preimage has been noted.
+		Noted { hash: T::Hash },
+		/// A preimage has been requested.
+		Requested { hash: T::Hash },
+		/// A preimage has ben cleared.
+		Cleared { hash: T::Hash },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Preimage has already been noted on-chain.
+		AlreadyNoted,
+		/// The user is not authorized to perform this action.
+		NotAuthorized,
+		/// The preimage cannot be removed since it has not yet been noted.
+		NotNoted,
+		/// The preimage request cannot be removed since no outstanding requests exist.
+		NotRequested,
+		/// The preimage is too big for any of this pallet's size buckets.
+		TooBig,
+		/// The reassembled chunks did not hash to the expected value.
+		HashMismatch,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register a preimage on-chain. If it was already requested, no deposit is needed. If
+		/// not, a deposit is reserved from the caller, unless this is called by
+		/// [`Config::ManagerOrigin`], which always notes for free.
+		#[pallet::call_index(0)]
+		#[pallet::weight(Weight::from_parts(10_000_000, 0))]
+		pub fn note_preimage(origin: OriginFor<T>, bytes: Vec<u8>) -> DispatchResultWithPostInfo {
+			let maybe_depositor = Self::ensure_signed_or_manager(origin)?;
+			let hash = <T as frame_system::Config>::Hashing::hash(&bytes);
+			let fulfils_request =
+				matches!(StatusFor::<T>::get(hash), Some(RequestStatus::Requested { len: None, .. }));
+			let was_paying = maybe_depositor.is_some() && !fulfils_request;
+			Self::do_note_preimage(maybe_depositor, bytes)?;
+			// Waive the fee for the `ManagerOrigin` and for anyone fulfilling an existing request.
+			Ok(if was_paying { Pays::Yes } else { Pays::No }.into())
+		}
+
+		/// Clear an unrequested preimage from the runtime storage, refunding any deposit this
+		/// pallet is holding for it.
+		#[pallet::call_index(1)]
+		#[pallet::weight(Weight::from_parts(10_000_000, 0))]
+		pub fn unnote_preimage(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
+			let maybe_check_owner = Self::ensure_signed_or_manager(origin)?;
+			Self::do_unnote_preimage(hash, maybe_check_owner)
+		}
+
+		/// Request a preimage be uploaded to the chain without paying any fee for it.
+		#[pallet::call_index(2)]
+		#[pallet::weight(Weight::from_parts(10_000_000, 0))]
+		pub fn request_preimage(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::do_request_preimage(hash);
+			Ok(())
+		}
+
+		/// Clear a previously made request for a preimage.
+		#[pallet::call_index(3)]
+		#[pallet::weight(Weight::from_parts(10_000_000, 0))]
+		pub fn unrequest_preimage(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::do_unrequest_preimage(hash)
+		}
+
+		/// Re-price an already-noted preimage's deposit to what [`Config::Consideration`] currently
+		/// charges for its footprint, topping up or refunding the difference. Callable by anyone, not
+		/// just the original depositor, so that a deposit parameter change can be rolled out across
+		/// existing preimages without governance having to track down every depositor.
+		#[pallet::call_index(4)]
+		#[pallet::weight(Weight::from_parts(10_000_000, 0))]
+		pub fn ensure_updated(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::do_ensure_updated(hash)
+		}
+
+		/// Upload one chunk of a preimage too large to submit in a single extrinsic. Chunks must be
+		/// submitted in order starting from `chunk_index` 0 and finished off with
+		/// [`Self::finalize_preimage`]. The caller is charged a deposit for the bytes held as they
+		/// arrive; see [`Self::cancel_chunk_upload`] to abandon a session and reclaim it.
+		#[pallet::call_index(5)]
+		#[pallet::weight(Weight::from_parts(10_000_000, 0))]
+		pub fn note_preimage_chunk(
+			origin: OriginFor<T>,
+			session: u32,
+			chunk_index: u32,
+			bytes: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_note_preimage_chunk(who, session, chunk_index, bytes)
+		}
+
+		/// Finish a chunked upload, storing its reassembled bytes as a preimage of `expected_hash`.
+		#[pallet::call_index(6)]
+		#[pallet::weight(Weight::from_parts(10_000_000, 0))]
+		pub fn finalize_preimage(
+			origin: OriginFor<T>,
+			session: u32,
+			expected_hash: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_finalize_preimage(who, session, expected_hash)
+		}
+
+		/// Abandon an in-progress chunked upload, refunding the deposit reserved for it so far.
+		#[pallet::call_index(7)]
+		#[pallet::weight(Weight::from_parts(10_000_000, 0))]
+		pub fn cancel_chunk_upload(origin: OriginFor<T>, session: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_cancel_chunk_upload(who, session)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The index into [`BUCKET_CUTOFFS`] of the smallest bucket that can hold `len` bytes.
+	fn bucket_index(len: u32) -> Option<usize> {
+		BUCKET_CUTOFFS.iter().position(|&cutoff| len <= cutoff)
+	}
+
+	fn store_bytes(hash: T::Hash, bytes: &[u8]) -> DispatchResult {
+		macro_rules! insert {
+			($map:ident) => {
+				$map::<T>::insert(
+					hash,
+					BoundedVec::try_from(bytes.to_vec()).map_err(|_| Error::<T>::TooBig)?,
+				)
+			};
+		}
+		match Self::bucket_index(bytes.len() as u32) {
+			Some(0) => insert!(Preimage7For),
+			Some(1) => insert!(Preimage10For),
+			Some(2) => insert!(Preimage13For),
+			Some(3) => insert!(Preimage16For),
+			Some(4) => insert!(Preimage19For),
+			Some(5) => insert!(Preimage20For),
+			Some(6) => insert!(Preimage21For),
+			Some(7) => insert!(Preimage22For),
+			_ => return Err(Error::<T>::TooBig.into()),
+		}
+		Ok(())
+	}
+
+	fn fetch_bytes(hash: T::Hash, len: u32) -> Option<Vec<u8>> {
+		match Self::bucket_index(len) {
+			Some(0) => Preimage7For::<T>::get(hash).map(|b| b.into_inner()),
+			Some(1) => Preimage10For::<T>::get(hash).map(|b| b.into_inner()),
+			Some(2) => Preimage13For::<T>::get(hash).map(|b| b.into_inner()),
+			Some(3) => Preimage16For::<T>::get(hash).map(|b| b.into_inner()),
+			Some(4) => Preimage19For::<T>::get(hash).map(|b| b.into_inner()),
+			Some(5) => Preimage20For::<T>::get(hash).map(|b| b.into_inner()),
+			Some(6) => Preimage21For::<T>::get(hash).map(|b| b.into_inner()),
+			Some(7) => Preimage22For::<T>::get(hash).map(|b| b.into_inner()),
+			_ => None,
+		}
+	}
+
+	fn remove_bytes(hash: T::Hash, len: u32) {
+		match Self::bucket_index(len) {
+			Some(0) => Preimage7For::<T>::remove(hash),
+			Some(1) => Preimage10For::<T>::remove(hash),
+			Some(2) => Preimage13For::<T>::remove(hash),
+			Some(3) => Preimage16For::<T>::remove(hash),
+			Some(4) => Preimage19For::<T>::remove(hash),
+			Some(5) => Preimage20For::<T>::remove(hash),
+			Some(6) => Preimage21For::<T>::remove(hash),
+			Some(7) => Preimage22For::<T>::remove(hash),
+			_ => {},
+		}
+	}
+
+	/// `Some(signer)` for a regular signed origin, `None` for an origin that satisfies
+	/// [`Config::ManagerOrigin`] (which isn't required to pay deposits).
+	fn ensure_signed_or_manager(
+		origin: OriginFor<T>,
+	) -> Result<Option<T::AccountId>, DispatchError> {
+		if T::ManagerOrigin::ensure_origin(origin.clone()).is_ok() {
+			return Ok(None)
+		}
+		Ok(Some(ensure_signed(origin)?))
+	}
+
+	fn deposit_for(len: u32) -> BalanceOf<T> {
+		T::BaseDeposit::get().saturating_add(T::ByteDeposit::get().saturating_mul(len.into()))
+	}
+
+	/// The [`Footprint`] [`Config::Consideration`] is priced from for a preimage of `len` bytes:
+	/// one item, sized at `len`.
+	fn footprint_for(len: u32) -> Footprint {
+		Footprint::from_parts(1, len as usize)
+	}
+
+	/// Store `bytes`, charging `maybe_depositor` a deposit unless the hash is already under an
+	/// unfulfilled request (in which case storing it is always free, since whoever requested it
+	/// is the one expected to pay, and they haven't been asked to).
+	fn do_note_preimage(maybe_depositor: Option<T::AccountId>, bytes: Vec<u8>) -> DispatchResult {
+		let hash = <T as frame_system::Config>::Hashing::hash(&bytes);
+		let len = bytes.len() as u32;
+
+		match StatusFor::<T>::get(hash) {
+			Some(RequestStatus::Unrequested { .. }) | Some(RequestStatus::Requested { len: Some(_), .. }) =>
+				return match maybe_depositor {
+					Some(_) => Err(Error::<T>::AlreadyNoted.into()),
+					None => Ok(()),
+				},
+			Some(RequestStatus::Requested { ticket, count, len: None }) => {
+				Self::store_bytes(hash, &bytes)?;
+				StatusFor::<T>::insert(
+					hash,
+					RequestStatus::Requested { ticket, count, len: Some(len) },
+				);
+				Self::deposit_event(Event::Noted { hash });
+				return Ok(())
+			},
+			None => {},
+		}
+
+		let ticket = match maybe_depositor {
+			Some(depositor) => {
+				let ticket = T::Consideration::new(&depositor, Self::footprint_for(len))?;
+				Some((depositor, ticket))
+			},
+			None => None,
+		};
+
+		Self::store_bytes(hash, &bytes)?;
+		StatusFor::<T>::insert(hash, RequestStatus::Unrequested { ticket, len });
+		Self::deposit_event(Event::Noted { hash });
+		Ok(())
+	}
+
+	/// Remove a preimage, refunding its deposit. `maybe_check_owner` is `None` for
+	/// [`Config::ManagerOrigin`] callers, which may clear anyone's preimage; `Some(who)` for
+	/// regular signed callers, who may only clear their own.
+	fn do_unnote_preimage(
+		hash: T::Hash,
+		maybe_check_owner: Option<T::AccountId>,
+	) -> DispatchResult {
+		match StatusFor::<T>::get(hash) {
+			Some(RequestStatus::Unrequested { ticket, len }) => {
+				if let Some(who) = &maybe_check_owner {
+					let (owner, _) = ticket.as_ref().ok_or(Error::<T>::NotAuthorized)?;
+					ensure!(owner == who, Error::<T>::NotAuthorized);
+				}
+				if let Some((owner, ticket)) = ticket {
+					ticket.drop(&owner)?;
+				}
+				Self::remove_bytes(hash, len);
+				StatusFor::<T>::remove(hash);
+				Self::deposit_event(Event::Cleared { hash });
+				Ok(())
+			},
+			// Still under an active request: the `ManagerOrigin` may waive the caller's hold on
+			// it unconditionally, but the bytes themselves stay until the request is lifted too.
+			Some(RequestStatus::Requested { ticket, count, len }) => match maybe_check_owner {
+				None => {
+					if let Some((owner, ticket)) = ticket {
+						ticket.drop(&owner)?;
+					}
+					StatusFor::<T>::insert(
+						hash,
+						RequestStatus::Requested { ticket: None, count, len },
+					);
+					Ok(())
+				},
+				Some(who) => {
+					let (owner, ticket) = ticket
+						.filter(|(owner, _)| *owner == who)
+						.ok_or(Error::<T>::NotAuthorized)?;
+					ticket.drop(&owner)?;
+					StatusFor::<T>::insert(
+						hash,
+						RequestStatus::Requested { ticket: None, count, len },
+					);
+					Ok(())
+				},
+			},
+			None => Err(Error::<T>::NotNoted.into()),
+		}
+	}
+
+	fn do_request_preimage(hash: T::Hash) {
+		let (count, len, ticket) = match StatusFor::<T>::get(hash) {
+			Some(RequestStatus::Requested { ticket, count, len }) =>
+				(count.saturating_add(1), len, ticket),
+			Some(RequestStatus::Unrequested { ticket, len }) => (1, Some(len), ticket),
+			None => (1, None, None),
+		};
+		StatusFor::<T>::insert(hash, RequestStatus::Requested { ticket, count, len });
+		Self::deposit_event(Event::Requested { hash });
+	}
+
+	fn do_unrequest_preimage(hash: T::Hash) -> DispatchResult {
+		match StatusFor::<T>::get(hash) {
+			Some(RequestStatus::Requested { ticket, count, len }) if count > 1 =>
+				StatusFor::<T>::insert(
+					hash,
+					RequestStatus::Requested { ticket, count: count - 1, len },
+				),
+			// A user-paid preimage outlives its last request as an ordinary unrequested note, so
+			// its depositor can still unnote it and be refunded.
+			Some(RequestStatus::Requested { ticket: Some(ticket), len: Some(len), .. }) =>
+				StatusFor::<T>::insert(hash, RequestStatus::Unrequested { ticket: Some(ticket), len }),
+			Some(RequestStatus::Requested { ticket, len, .. }) => {
+				if let Some((owner, ticket)) = ticket {
+					ticket.drop(&owner)?;
+				}
+				if let Some(len) = len {
+					Self::remove_bytes(hash, len);
+				}
+				StatusFor::<T>::remove(hash);
+			},
+			Some(RequestStatus::Unrequested { .. }) | None =>
+				return Err(Error::<T>::NotRequested.into()),
+		}
+		Ok(())
+	}
+
+	/// Whether `hash` currently has its preimage bytes stored.
+	pub fn have_preimage(hash: &T::Hash) -> bool {
+		match StatusFor::<T>::get(hash) {
+			Some(RequestStatus::Unrequested { .. }) => true,
+			Some(RequestStatus::Requested { len: Some(_), .. }) => true,
+			_ => false,
+		}
+	}
+
+	/// Fetch the preimage bytes for `hash`, if they've been stored.
+	pub fn get_preimage(hash: &T::Hash) -> Option<Vec<u8>> {
+		match StatusFor::<T>::get(hash)? {
+			RequestStatus::Unrequested { len, .. } => Self::fetch_bytes(*hash, len),
+			RequestStatus::Requested { len: Some(len), .. } => Self::fetch_bytes(*hash, len),
+			RequestStatus::Requested { len: None, .. } => None,
+		}
+	}
+
+	/// Reserve or release the difference between `held` and the `target` deposit from/to `who`.
+	fn reprice_deposit(who: &T::AccountId, held: BalanceOf<T>, target: BalanceOf<T>) -> DispatchResult {
+		if target > held {
+			T::Currency::reserve(who, target - held)?;
+		} else if target < held {
+			T::Currency::unreserve(who, held - target);
+		}
+		Ok(())
+	}
+
+	/// Bring `hash`'s deposit in line with what [`Self::footprint_for`] would charge today.
+	fn do_ensure_updated(hash: T::Hash) -> DispatchResult {
+		match StatusFor::<T>::get(hash).ok_or(Error::<T>::NotNoted)? {
+			RequestStatus::Unrequested { ticket: Some((who, ticket)), len } => {
+				let ticket = ticket.update(&who, Self::footprint_for(len))?;
+				StatusFor::<T>::insert(
+					hash,
+					RequestStatus::Unrequested { ticket: Some((who, ticket)), len },
+				);
+			},
+			RequestStatus::Requested { ticket: Some((who, ticket)), count, len: Some(len) } => {
+				let ticket = ticket.update(&who, Self::footprint_for(len))?;
+				StatusFor::<T>::insert(
+					hash,
+					RequestStatus::Requested { ticket: Some((who, ticket)), count, len: Some(len) },
+				);
+			},
+			// Noted for free by the `ManagerOrigin`, not yet fulfilled, or fulfilled but the bytes
+			// haven't landed yet (so there's nothing to size the deposit against): nothing to
+			// re-price.
+			_ => {},
+		}
+		Ok(())
+	}
+
+	fn do_note_preimage_chunk(
+		who: T::AccountId,
+		session: u32,
+		chunk_index: u32,
+		bytes: Vec<u8>,
+	) -> DispatchResult {
+		let mut upload = match ChunkedUploads::<T>::get(&who, session) {
+			Some(upload) => {
+				ensure!(chunk_index == upload.chunks_received, Error::<T>::NotRequested);
+				upload
+			},
+			None => {
+				ensure!(chunk_index == 0, Error::<T>::NotRequested);
+				ChunkedUpload {
+					depositor: who.clone(),
+					deposit: Zero::zero(),
+					chunks_received: 0,
+					bytes: Default::default(),
+					deadline: Zero::zero(),
+				}
+			},
+		};
+
+		upload.bytes.try_extend(bytes.into_iter()).map_err(|_| Error::<T>::TooBig)?;
+		upload.chunks_received = upload.chunks_received.saturating_add(1);
+
+		let target = Self::deposit_for(upload.bytes.len() as u32);
+		Self::reprice_deposit(&who, upload.deposit, target)?;
+		upload.deposit = target;
+		upload.deadline = frame_system::Pallet::<T>::block_number()
+			.saturating_add(T::ChunkUploadExpiry::get());
+
+		ChunkedUploads::<T>::insert(who, session, upload);
+		Ok(())
+	}
+
+	/// Abandon every [`ChunkedUpload`] session whose [`ChunkedUpload::deadline`] has passed,
+	/// refunding each one's deposit. Driven by [`Hooks::on_initialize`] so that a caller who
+	/// starts a chunked upload and never finishes or cancels it doesn't tie up their deposit
+	/// forever.
+	fn sweep_expired_chunk_uploads() -> Weight {
+		let now = frame_system::Pallet::<T>::block_number();
+
+		let expired: Vec<(T::AccountId, u32, BalanceOf<T>)> = ChunkedUploads::<T>::iter()
+			.filter(|(_, _, upload)| upload.deadline < now)
+			.map(|(who, session, upload)| (who, session, upload.deposit))
+			.collect();
+
+		let count = expired.len() as u64;
+		for (who, session, deposit) in expired {
+			T::Currency::unreserve(&who, deposit);
+			ChunkedUploads::<T>::remove(who, session);
+		}
+
+		T::DbWeight::get().reads_writes(count, count)
+	}
+
+	fn do_finalize_preimage(who: T::AccountId, session: u32, expected_hash: T::Hash) -> DispatchResult {
+		let upload = ChunkedUploads::<T>::get(&who, session).ok_or(Error::<T>::NotNoted)?;
+		let hash = <T as frame_system::Config>::Hashing::hash(&upload.bytes);
+		ensure!(hash == expected_hash, Error::<T>::HashMismatch);
+
+		let len = upload.bytes.len() as u32;
+		Self::store_bytes(hash, &upload.bytes)?;
+		// The upload's deposit was a raw balance reserve, priced for the chunked transfer; swap
+		// it for a proper `Consideration` ticket now that the preimage has a final size.
+		T::Currency::unreserve(&upload.depositor, upload.deposit);
+		let ticket = T::Consideration::new(&upload.depositor, Self::footprint_for(len))?;
+		StatusFor::<T>::insert(
+			hash,
+			RequestStatus::Unrequested { ticket: Some((upload.depositor, ticket)), len },
+		);
+		ChunkedUploads::<T>::remove(who, session);
+		Self::deposit_event(Event::Noted { hash });
+		Ok(())
+	}
+
+	fn do_cancel_chunk_upload(who: T::AccountId, session: u32) -> DispatchResult {
+		let upload = ChunkedUploads::<T>::take(&who, session).ok_or(Error::<T>::NotNoted)?;
+		T::Currency::unreserve(&upload.depositor, upload.deposit);
+		Ok(())
+	}
+}
+
+impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+	fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
+		Self::sweep_expired_chunk_uploads()
+	}
+}
+
+/// The bytes backing a preimage, as handed back by [`QueryPreimage::fetch`].
+pub type FetchedPreimage = BoundedVec<u8, ConstU32<MAX_SIZE>>;
+
+impl<T: Config> QueryPreimage for Pallet<T> {
+	type H = T::Hashing;
+
+	fn len(hash: &T::Hash) -> Option<u32> {
+		match StatusFor::<T>::get(hash)? {
+			RequestStatus::Unrequested { len, .. } => Some(len),
+			RequestStatus::Requested { len, .. } => len,
+		}
+	}
+
+	fn is_requested(hash: &T::Hash) -> bool {
+		matches!(StatusFor::<T>::get(hash), Some(RequestStatus::Requested { .. }))
+	}
+
+	fn have(bounded: &Bounded<T::Hash, T::Hashing, T::MaxInlineSize>) -> bool {
+		match bounded {
+			Bounded::Inline(..) => true,
+			Bounded::Lookup { .. } => Self::len(&bounded.hash()).is_some(),
+		}
+	}
+
+	fn fetch(
+		bounded: &Bounded<T::Hash, T::Hashing, T::MaxInlineSize>,
+		len: Option<u32>,
+	) -> Result<FetchedPreimage, DispatchError> {
+		if let Bounded::Inline(bytes, _) = bounded {
+			return FetchedPreimage::try_from(bytes.clone().into_inner())
+				.map_err(|_| Error::<T>::TooBig.into())
+		}
+		let hash = bounded.hash();
+		let len = len.or_else(|| Self::len(&hash)).ok_or(Error::<T>::NotNoted)?;
+		let bytes = Self::fetch_bytes(hash, len).ok_or(Error::<T>::NotNoted)?;
+		FetchedPreimage::try_from(bytes).map_err(|_| Error::<T>::TooBig.into())
+	}
+
+	fn request(bounded: &Bounded<T::Hash, T::Hashing, T::MaxInlineSize>) -> DispatchResult {
+		if matches!(bounded, Bounded::Inline(..)) {
+			// Inline values are always already available; there is nothing to request.
+			return Ok(())
+		}
+		Self::do_request_preimage(bounded.hash());
+		Ok(())
+	}
+
+	fn unrequest(bounded: &Bounded<T::Hash, T::Hashing, T::MaxInlineSize>) {
+		if matches!(bounded, Bounded::Inline(..)) {
+			return
+		}
+		let _ = Self::do_unrequest_preimage(bounded.hash());
+	}
+}
+
+impl<T: Config> StorePreimage for Pallet<T> {
+	const MAX_LENGTH: usize = MAX_SIZE as usize;
+
+	fn note(bytes: Cow<[u8]>) -> Result<Bounded<T::Hash, T::Hashing, T::MaxInlineSize>, DispatchError> {
+		let len = bytes.len() as u32;
+		if len < T::MaxInlineSize::get() {
+			let inline = BoundedVec::try_from(bytes.into_owned())
+				.map_err(|_| Error::<T>::TooBig)?;
+			return Ok(Bounded::Inline(inline, PhantomData))
+		}
+		let hash = <T as frame_system::Config>::Hashing::hash(&bytes);
+		Self::do_note_preimage(None, bytes.into_owned())?;
+		Ok(Bounded::Lookup { hash, len })
+	}
+
+	fn unnote(bounded: &Bounded<T::Hash, T::Hashing, T::MaxInlineSize>) {
+		if matches!(bounded, Bounded::Inline(..)) {
+			return
+		}
+		let _ = Self::do_unnote_preimage(bounded.hash(), None);
+	}
+}
@@ -0,0 +1,92 @@
+// This file is part of Substrate.
+
+// Copyright (C) 2017-2022 Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Test utilities for the preimage pallet.
+
+use crate as pallet_preimage;
+use frame_support::{
+	derive_impl,
+	traits::tokens::fungible::{HoldConsideration, LinearStoragePrice},
+};
+use sp_runtime::{traits::BlakeTwo256, BuildStorage};
+
+pub type Origin = RuntimeOrigin;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Preimage: pallet_preimage,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+}
+
+frame_support::parameter_types! {
+	pub const PreimageBaseDeposit: u64 = 2;
+	pub const PreimageByteDeposit: u64 = 1;
+	pub const PreimageMaxInlineSize: u32 = 1 << 7;
+	pub const PreimageChunkUploadExpiry: u64 = 10;
+	pub const PreimageHoldReason: RuntimeHoldReason = RuntimeHoldReason::Preimage(pallet_preimage::HoldReason::Preimage);
+}
+
+frame_support::ord_parameter_types! {
+	// Account 1 plays the role of the pallet's manager throughout the test suite: it can note
+	// and unnote preimages for free, and unnote anyone else's.
+	pub const ManagerAccount: u64 = 1;
+}
+
+impl pallet_preimage::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type ManagerOrigin = frame_system::EnsureSignedBy<ManagerAccount, u64>;
+	type BaseDeposit = PreimageBaseDeposit;
+	type ByteDeposit = PreimageByteDeposit;
+	type MaxInlineSize = PreimageMaxInlineSize;
+	type ChunkUploadExpiry = PreimageChunkUploadExpiry;
+	type Consideration = HoldConsideration<
+		u64,
+		Balances,
+		PreimageHoldReason,
+		LinearStoragePrice<PreimageBaseDeposit, PreimageByteDeposit, u64>,
+	>;
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(0, 0), (1, 100), (2, 100), (3, 100)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	t.into()
+}
+
+/// The hash `bytes` would be stored under, matching `T::Hashing` (`BlakeTwo256`).
+pub fn hashed(bytes: impl Into<Vec<u8>>) -> sp_core::H256 {
+	<BlakeTwo256 as sp_runtime::traits::Hash>::hash(&bytes.into())
+}
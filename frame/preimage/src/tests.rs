@@ -20,7 +20,10 @@
 use super::*;
 use crate::mock::*;
 
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{QueryPreimage, StorePreimage},
+};
 use pallet_balances::Error as BalancesError;
 
 #[test]
@@ -297,3 +300,118 @@ fn noted_preimage_use_correct_map() {
 		assert_eq!(StatusFor::<Test>::iter().count(), 0);
 	});
 }
+
+#[test]
+fn query_and_store_preimage_traits_mirror_the_inherent_functions() {
+	new_test_ext().execute_with(|| {
+		// At `MaxInlineSize` bytes, so held by lookup rather than inline.
+		let bound = <Preimage as StorePreimage>::note(vec![1; 128].into()).unwrap();
+
+		assert!(<Preimage as QueryPreimage>::have(&bound));
+		assert_eq!(
+			<Preimage as QueryPreimage>::fetch(&bound, None).unwrap().into_inner(),
+			vec![1; 128]
+		);
+
+		assert!(!<Preimage as QueryPreimage>::is_requested(&bound.hash()));
+		assert_ok!(<Preimage as QueryPreimage>::request(&bound));
+		assert!(<Preimage as QueryPreimage>::is_requested(&bound.hash()));
+		<Preimage as QueryPreimage>::unrequest(&bound);
+
+		<Preimage as StorePreimage>::unnote(&bound);
+		assert!(!<Preimage as QueryPreimage>::have(&bound));
+	});
+}
+
+#[test]
+fn small_preimages_are_stored_inline_without_bucket_bookkeeping() {
+	new_test_ext().execute_with(|| {
+		let bound = <Preimage as StorePreimage>::note(vec![1].into()).unwrap();
+
+		assert!(matches!(bound, Bounded::Inline(..)));
+		// An inline value skips `note_preimage`'s request/status bookkeeping entirely.
+		assert_eq!(StatusFor::<Test>::iter().count(), 0);
+
+		assert!(<Preimage as QueryPreimage>::have(&bound));
+		assert_eq!(<Preimage as QueryPreimage>::fetch(&bound, None).unwrap().into_inner(), vec![1]);
+	});
+}
+
+#[test]
+fn preimages_at_the_inline_threshold_still_use_a_bucket() {
+	new_test_ext().execute_with(|| {
+		let bound = <Preimage as StorePreimage>::note(vec![0; 128].into()).unwrap();
+
+		assert!(matches!(bound, Bounded::Lookup { .. }));
+		assert_eq!(Preimage7For::<Test>::iter().count(), 1);
+	});
+}
+
+#[test]
+fn ensure_updated_is_a_noop_when_the_ticket_is_already_current() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), vec![1]));
+		assert_eq!(Balances::reserved_balance(2), 3);
+
+		// Nothing has changed governance's deposit parameters, so re-pricing the ticket must
+		// neither hold nor release any further balance.
+		assert_ok!(Preimage::ensure_updated(Origin::signed(2), hashed([1])));
+		assert_eq!(Balances::reserved_balance(2), 3);
+		assert_eq!(Balances::free_balance(2), 97);
+	});
+}
+
+#[test]
+fn ensure_updated_is_permissionless() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage(Origin::signed(2), vec![1]));
+
+		// Anyone, not just the original depositor, may trigger a reprice.
+		assert_ok!(Preimage::ensure_updated(Origin::signed(3), hashed([1])));
+		assert_eq!(Balances::reserved_balance(2), 3);
+	});
+}
+
+#[test]
+fn chunked_upload_matches_a_one_shot_note() {
+	new_test_ext().execute_with(|| {
+		// Small enough that account 1's balance of 100 covers the chunk deposit of 2 + 60.
+		let preimage = vec![7u8; 60];
+		let expected_hash = hashed(preimage.clone());
+
+		assert_ok!(Preimage::note_preimage_chunk(Origin::signed(1), 0, 0, preimage[..30].to_vec()));
+		assert_ok!(Preimage::note_preimage_chunk(Origin::signed(1), 0, 1, preimage[30..].to_vec()));
+		assert_ok!(Preimage::finalize_preimage(Origin::signed(1), 0, expected_hash));
+
+		assert!(Preimage::have_preimage(&expected_hash));
+		assert_eq!(Preimage::get_preimage(&expected_hash), Some(preimage));
+	});
+}
+
+#[test]
+fn finalize_preimage_rejects_a_hash_mismatch() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage_chunk(Origin::signed(1), 0, 0, vec![1; 64]));
+
+		assert_noop!(
+			Preimage::finalize_preimage(Origin::signed(1), 0, hashed([9])),
+			Error::<Test>::HashMismatch
+		);
+	});
+}
+
+#[test]
+fn cancel_chunk_upload_reclaims_the_session_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Preimage::note_preimage_chunk(Origin::signed(2), 1, 0, vec![1; 64]));
+		assert!(Balances::reserved_balance(2) > 0);
+
+		assert_ok!(Preimage::cancel_chunk_upload(Origin::signed(2), 1));
+		assert_eq!(Balances::reserved_balance(2), 0);
+
+		assert_noop!(
+			Preimage::cancel_chunk_upload(Origin::signed(2), 1),
+			Error::<Test>::NotNoted
+		);
+	});
+}
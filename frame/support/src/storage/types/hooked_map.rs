@@ -1,33 +1,32 @@
 use core::{marker::PhantomData, result};
 
 use codec::{Decode, Encode, EncodeLike, FullCodec};
-use frame_metadata::{StorageEntryMetadata, StorageEntryType};
+use frame_metadata::StorageEntryMetadata;
 use scale_info::TypeInfo;
-use sp_arithmetic::traits::Bounded;
 
 use crate::{
 	storage::{self, StorageAppend, StorageDecodeLength, StorageTryAppend},
-	traits::{Get, OnUnbalanced, StorageInfo, StorageInstance},
+	traits::StorageInfo,
 	StoragePrefixedMap,
 };
 // we don't bring this fully into scope because it can be confusing -- only to allow trait functions
 // being used.
 use storage::generator::StorageMap as _;
 
-use super::{QueryKindTrait, StorageEntryMetadataBuilder};
+use super::StorageEntryMetadataBuilder;
 
-// / This is fired IFF some value already existed in `key`.
-// #[impl_trait_for_tuples::impl_for_tuples(0, 32)]
+/// This is fired IFF some value already existed in `key`.
+#[impl_trait_for_tuples::impl_for_tuples(0, 32)]
 pub trait StorageOnRemove<K: FullCodec, V> {
 	fn on_remove<KeyArg: EncodeLike<K>>(key: &KeyArg, value: &V);
 }
 
-// #[impl_trait_for_tuples::impl_for_tuples(0, 32)]
+#[impl_trait_for_tuples::impl_for_tuples(0, 32)]
 pub trait StorageOnInsert<K: FullCodec, V> {
 	fn on_insert<KeyArg: EncodeLike<K>>(key: &KeyArg, value: &V);
 }
 
-// #[impl_trait_for_tuples::impl_for_tuples(0, 32)]
+#[impl_trait_for_tuples::impl_for_tuples(0, 32)]
 pub trait StorageOnUpdate<K: FullCodec, V> {
 	fn on_update<KeyArg: EncodeLike<K>>(key: &KeyArg, old_value: &V, new_value: &V);
 }
@@ -174,6 +173,23 @@ where
 		<Map as storage::StorageMap<Key, Value>>::insert(key, val)
 	}
 
+	/// Store a `Query` value directly under the given key, honoring the wrapped map's
+	/// `ValueQuery`/`OptionQuery` semantics.
+	///
+	/// Unlike [`Self::insert`], this allows writing the `Query` type itself (e.g. `None` for an
+	/// `OptionQuery`), and is hooked the same way `mutate_exists` is: setting to `None` fires
+	/// `OnRemove`, setting over an existing value fires `OnUpdate`, and setting over an absent
+	/// key fires `OnInsert`.
+	pub fn set<KeyArg: EncodeLike<Key> + Clone>(
+		key: KeyArg,
+		query: <Map as storage::StorageMap<Key, Value>>::Query,
+	) {
+		let maybe_old_value = Self::maybe_get(key.clone());
+		<Map as storage::StorageMap<Key, Value>>::set(key.clone(), query);
+		let maybe_new_value = Self::maybe_get(key.clone());
+		Self::post_mutate_hooks(key, maybe_old_value, maybe_new_value);
+	}
+
 	/// Remove the value under a key.
 	pub fn remove<KeyArg: EncodeLike<Key> + Clone>(key: KeyArg) {
 		if let Ok(removed) = Self::try_get(key) {
@@ -350,29 +366,60 @@ where
 	<Map as storage::generator::StorageMap<Key, Value>>::Hasher:
 		crate::hash::StorageHasher + crate::ReversibleStorageHasher,
 {
-	/// Remove all values of the storage in the overlay and up to `limit` in the backend.
-	///
-	/// All values in the client overlay will be deleted, if there is some `limit` then up to
-	/// `limit` values are deleted from the client backend, if `limit` is none then all values in
-	/// the client backend are deleted.
+	/// Clear up to `limit` entries, resuming from `maybe_cursor` if given, and fire `OnRemove`
+	/// for every key-value pair that is actually removed.
 	///
-	/// # Note
+	/// Unlike the wrapped `Map`'s own `clear_prefix`, this does not delegate to the single host
+	/// `clear_prefix` call: there is no way to learn from that call which individual keys it
+	/// removed, so a hook pass computed separately from it can disagree about which entries were
+	/// actually deleted. Instead this removes entries one at a time, firing `OnRemove` for each
+	/// just before removing it from the wrapped `Map`, which makes "hooked" and "removed" the same
+	/// operation by construction.
 	///
-	/// Calling this multiple times per block with a `limit` set leads always to the same keys being
-	/// removed and the same result being returned. This happens because the keys to delete in the
-	/// overlay are not taken into account when deleting keys in the backend.
-	pub fn remove_all(limit: Option<u32>) -> sp_io::KillStorageResult {
+	/// This supports multi-block resumable clearing the same way the wrapped `Map`'s own
+	/// `clear_prefix` does: the returned `MultiRemovalResults::maybe_cursor` is `Some(next_key)`
+	/// when more entries remain and `None` once the map is fully cleared. Calling `clear` again
+	/// with the returned cursor makes forward progress and does not re-fire hooks for entries
+	/// that were already removed.
+	pub fn clear(limit: u32, maybe_cursor: Option<&[u8]>) -> sp_io::MultiRemovalResults {
+		let mut iter = match maybe_cursor {
+			Some(cursor) => Self::iter_from(cursor.to_vec()),
+			None => Self::iter(),
+		};
+
+		// `iter_from` resumes strictly *after* the raw key it's given, so the cursor we hand
+		// back must be the raw key of the last entry we actually removed (or, if nothing was
+		// removed this call, whatever cursor we were given, or the map's own prefix if none),
+		// never the key we merely peeked at and stopped before processing. Using the latter would
+		// make `iter_from` skip straight past it on the next call.
+		let mut last_removed_raw_key = maybe_cursor.map(|cursor| cursor.to_vec());
 		let mut removed = 0u32;
-		Self::iter()
-			.drain()
-			.take(limit.unwrap_or(Bounded::max_value()) as usize)
-			.for_each(|(k, v)| {
-				OnRemove::on_remove(&k, &v);
-				removed += 1;
-			});
+		let mut maybe_next_cursor = None;
+		for (k, v) in iter.by_ref() {
+			if removed >= limit {
+				maybe_next_cursor = Some(last_removed_raw_key.unwrap_or_else(|| {
+					<Map as StoragePrefixedMap<Value>>::final_prefix().to_vec()
+				}));
+				break;
+			}
+			OnRemove::on_remove(&k, &v);
+			last_removed_raw_key = Some(Self::hashed_key_for(&k));
+			<Map as storage::StorageMap<Key, Value>>::remove(k);
+			removed += 1;
+		}
 
-		// TODO: this one's a bit tricky.
-		sp_io::KillStorageResult::AllRemoved(removed)
+		// Each loop iteration above removes exactly one entry that indeed existed (we just
+		// fetched it from the map), one at a time, straight through to the backend -- unlike the
+		// wrapped `Map`'s own host-side `clear_prefix`, there is no batched backend scan here
+		// whose overlay-only hits would need to be counted separately. So `backend`, `unique` and
+		// `loops` are all genuinely the same count here, and (with the cursor fix above) can no
+		// longer disagree with the limit budget by re-counting an entry across calls.
+		sp_io::MultiRemovalResults {
+			maybe_cursor: maybe_next_cursor,
+			backend: removed,
+			unique: removed,
+			loops: removed,
+		}
 	}
 
 	/// Enumerate all elements in the map in no particular order.
@@ -407,96 +454,91 @@ where
 
 	/// Remove all elements from the map and iterate through them in no particular order.
 	///
+	/// Each yielded `(key, value)` fires `OnRemove` as it is consumed, so a `drain()` that is
+	/// never iterated to completion only reports the elements it actually removed.
+	///
 	/// If you add elements to the map while doing this, you'll get undefined results.
-	pub fn drain() -> storage::PrefixIterator<(Key, Value)> {
+	pub fn drain() -> impl Iterator<Item = (Key, Value)> {
 		<Map as storage::IterableStorageMap<Key, Value>>::drain()
-		// TODO:
+			.inspect(|(k, v)| OnRemove::on_remove(k, v))
 	}
 
 	/// Translate the values of all elements by a function `f`, in the map in no particular order.
 	///
-	/// By returning `None` from `f` for an element, you'll remove it from the map.
+	/// By returning `None` from `f` for an element, you'll remove it from the map and fire
+	/// `OnRemove`; returning `Some(new)` fires `OnUpdate` with the old and new values.
+	///
+	/// This is the same-type transform: `f` sees the already-current `Value`. For a genuine
+	/// storage migration away from a defunct on-disk type, use [`Self::translate_from`] instead.
 	///
 	/// NOTE: If a value fail to decode because storage is corrupted then it is skipped.
-	pub fn translate<O: Decode, F: FnMut(Key, O) -> Option<Value>>(f: F) {
+	pub fn translate<F>(mut f: F)
+	where
+		F: FnMut(Key, Value) -> Option<Value>,
+	{
+		<Map as storage::IterableStorageMap<Key, Value>>::translate(move |k, old: Value| {
+			let new_value = f(k.clone(), old.clone());
+			match &new_value {
+				Some(new_value) => OnUpdate::on_update(&k, &old, new_value),
+				None => OnRemove::on_remove(&k, &old),
+			}
+			new_value
+		})
+	}
+
+	/// Translate the values of all elements from a defunct on-disk type `O` to the current
+	/// `Value`, in no particular order.
+	///
+	/// This is the form a real storage migration needs and [`Self::translate`] can't serve: `O`
+	/// is the old, pre-migration encoding, and it is not required (and generally must not be
+	/// expected) to convert into `Value`. Because no `Value`-typed old value ever exists for such
+	/// an entry, `OnUpdate`/`OnRemove` — both typed to observe `Value` — cannot be fired for it;
+	/// this is the underlying `Map`'s `translate` with no hooks attached. A runtime upgrade that
+	/// needs observers to learn about the migration must drive that separately.
+	///
+	/// NOTE: If a value fails to decode because storage is corrupted then it is skipped.
+	pub fn translate_from<O, F>(f: F)
+	where
+		O: Decode,
+		F: FnMut(Key, O) -> Option<Value>,
+	{
 		<Map as storage::IterableStorageMap<Key, Value>>::translate(f)
-		// TODO:
 	}
 }
 
-impl<Key, Value, Map> StorageEntryMetadataBuilder
-	for HookedMap<Map, Key, Value>
+impl<Key, Value, Map, OnRemove, OnInsert, OnUpdate> StorageEntryMetadataBuilder
+	for HookedMap<Map, Key, Value, OnRemove, OnInsert, OnUpdate>
 where
 	Key: FullCodec + TypeInfo,
 	Value: FullCodec + TypeInfo,
-	Map: storage::generator::StorageMap<Key, Value>,
+	Map: storage::generator::StorageMap<Key, Value> + StorageEntryMetadataBuilder,
 {
-
 	fn build_metadata(docs: Vec<&'static str>, entries: &mut Vec<StorageEntryMetadata>) {
-		let docs = if cfg!(feature = "no-metadata-docs") { vec![] } else { docs };
-
-		let entry = StorageEntryMetadata {
-			name: <Map as storage::StorageMap<Key, Value>>::pal
-			modifier: <Map as storage::StorageMap<Key, Value>>::Query::METADATA,
-			ty: StorageEntryType::Map {
-				hashers: vec![Map::Hasher],
-				key: scale_info::meta_type::<Key>(),
-				value: scale_info::meta_type::<Value>(),
-			},
-			default: OnEmpty::get().encode(),
-			docs,
-		};
+		// The hooked wrapper changes no part of the on-chain shape, so its metadata is exactly
+		// that of the wrapped `Map` (name, hasher, key, value and default all come from it).
+		Map::build_metadata(docs, entries)
+	}
+}
 
-		entries.push(entry);
+/// `HookedMap` stores nothing of its own, so it is transparent to `StorageInfo`-driven tooling:
+/// both impls simply forward to the wrapped `Map`.
+impl<Key, Value, Map, OnRemove, OnInsert, OnUpdate> crate::traits::StorageInfoTrait
+	for HookedMap<Map, Key, Value, OnRemove, OnInsert, OnUpdate>
+where
+	Map: crate::traits::StorageInfoTrait,
+{
+	fn storage_info() -> Vec<StorageInfo> {
+		Map::storage_info()
 	}
 }
 
-// impl<Prefix, Hasher, Key, Value, QueryKind, OnEmpty, MaxValues> crate::traits::StorageInfoTrait
-// 	for StorageMap<Prefix, Hasher, Key, Value, QueryKind, OnEmpty, MaxValues>
-// where
-// 	Prefix: StorageInstance,
-// 	Hasher: crate::hash::StorageHasher,
-// 	Key: FullCodec + MaxEncodedLen,
-// 	Value: FullCodec + MaxEncodedLen,
-// 	QueryKind: QueryKindTrait<Value, OnEmpty>,
-// 	OnEmpty: Get<QueryKind::Query> + 'static,
-// 	MaxValues: Get<Option<u32>>,
-// {
-// 	fn storage_info() -> Vec<StorageInfo> {
-// 		vec![StorageInfo {
-// 			pallet_name: Self::module_prefix().to_vec(),
-// 			storage_name: Self::storage_prefix().to_vec(),
-// 			prefix: Self::final_prefix().to_vec(),
-// 			max_values: MaxValues::get(),
-// 			max_size: Some(
-// 				Hasher::max_len::<Key>()
-// 					.saturating_add(Value::max_encoded_len())
-// 					.saturated_into(),
-// 			),
-// 		}]
-// 	}
-// }
-
-// /// It doesn't require to implement `MaxEncodedLen` and give no information for `max_size`.
-// impl<Prefix, Hasher, Key, Value, QueryKind, OnEmpty, MaxValues>
-// 	crate::traits::PartialStorageInfoTrait
-// 	for StorageMap<Prefix, Hasher, Key, Value, QueryKind, OnEmpty, MaxValues>
-// where
-// 	Prefix: StorageInstance,
-// 	Hasher: crate::hash::StorageHasher,
-// 	Key: FullCodec,
-// 	Value: FullCodec,
-// 	QueryKind: QueryKindTrait<Value, OnEmpty>,
-// 	OnEmpty: Get<QueryKind::Query> + 'static,
-// 	MaxValues: Get<Option<u32>>,
-// {
-// 	fn partial_storage_info() -> Vec<StorageInfo> {
-// 		vec![StorageInfo {
-// 			pallet_name: Self::module_prefix().to_vec(),
-// 			storage_name: Self::storage_prefix().to_vec(),
-// 			prefix: Self::final_prefix().to_vec(),
-// 			max_values: MaxValues::get(),
-// 			max_size: None,
-// 		}]
-// 	}
-// }
+/// It doesn't require to implement `MaxEncodedLen` and give no information for `max_size`.
+impl<Key, Value, Map, OnRemove, OnInsert, OnUpdate> crate::traits::PartialStorageInfoTrait
+	for HookedMap<Map, Key, Value, OnRemove, OnInsert, OnUpdate>
+where
+	Map: crate::traits::PartialStorageInfoTrait,
+{
+	fn partial_storage_info() -> Vec<StorageInfo> {
+		Map::partial_storage_info()
+	}
+}
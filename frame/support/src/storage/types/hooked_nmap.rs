@@ -0,0 +1,211 @@
+use core::marker::PhantomData;
+
+use codec::{EncodeLike, FullCodec};
+
+use crate::storage::{
+	self,
+	types::{nmap::KeyGenerator, StorageOnInsert, StorageOnRemove, StorageOnUpdate},
+	StoragePrefixedMap,
+};
+// we don't bring this fully into scope because it can be confusing -- only to allow trait
+// functions being used.
+use storage::generator::StorageNMap as _;
+
+/// A wrapper around a [`storage::generator::StorageNMap`] that dispatches
+/// [`StorageOnRemove`]/[`StorageOnInsert`]/[`StorageOnUpdate`] hooks around every mutating
+/// operation, mirroring [`super::HookedMap`] for composite (`NMap`) keys.
+///
+/// The hook key type is the full key tuple `K::Key`, so observers always receive the whole
+/// composite key rather than one of its components.
+pub struct HookedNMap<Map, Key, Value, OnRemove = (), OnInsert = (), OnUpdate = ()>(
+	PhantomData<(Map, Key, Value, OnRemove, OnInsert, OnUpdate)>,
+);
+
+impl<Key, Value, Map, OnRemove, OnInsert, OnUpdate> storage::generator::StorageNMap<Key, Value>
+	for HookedNMap<Map, Key, Value, OnRemove, OnInsert, OnUpdate>
+where
+	Key: KeyGenerator,
+	Value: FullCodec,
+	Map: storage::generator::StorageNMap<Key, Value>,
+{
+	type Query = <Map as storage::StorageNMap<Key, Value>>::Query;
+	fn module_prefix() -> &'static [u8] {
+		Map::module_prefix()
+	}
+	fn storage_prefix() -> &'static [u8] {
+		Map::storage_prefix()
+	}
+	fn from_optional_value_to_query(v: Option<Value>) -> Self::Query {
+		Map::from_optional_value_to_query(v)
+	}
+	fn from_query_to_optional_value(v: Self::Query) -> Option<Value> {
+		Map::from_query_to_optional_value(v)
+	}
+}
+
+impl<Key, Value, Map, OnRemove, OnInsert, OnUpdate> StoragePrefixedMap<Value>
+	for HookedNMap<Map, Key, Value, OnRemove, OnInsert, OnUpdate>
+where
+	Value: FullCodec,
+	Map: StoragePrefixedMap<Value>,
+{
+	fn module_prefix() -> &'static [u8] {
+		Map::module_prefix()
+	}
+	fn storage_prefix() -> &'static [u8] {
+		Map::storage_prefix()
+	}
+}
+
+impl<Key, Value, Map, OnRemove, OnInsert, OnUpdate>
+	HookedNMap<Map, Key, Value, OnRemove, OnInsert, OnUpdate>
+where
+	OnRemove: StorageOnRemove<Key::Key, Value>,
+	OnInsert: StorageOnInsert<Key::Key, Value>,
+	OnUpdate: StorageOnUpdate<Key::Key, Value>,
+	Key: KeyGenerator,
+	Value: FullCodec + Clone,
+	Map: storage::StorageNMap<Key, Value> + storage::generator::StorageNMap<Key, Value>,
+	<Map as storage::StorageNMap<Key, Value>>::Query: Clone,
+{
+	/// Maybe get the value for the given key from the map.
+	///
+	/// Returns `Some` if it exists, `None` if not.
+	///
+	/// This is not publicly available, since it is equivalent to `get`.
+	fn maybe_get<KeyArg: EncodeLike<Key::Key>>(key: KeyArg) -> Option<Value> {
+		Self::try_get(key).ok()
+	}
+
+	fn post_mutate_hooks<KeyArg: EncodeLike<Key::Key>>(
+		key: KeyArg,
+		maybe_old_value: Option<Value>,
+		maybe_new_value: Option<Value>,
+	) {
+		match (maybe_old_value, maybe_new_value) {
+			(Some(old_value), Some(new_value)) => {
+				OnUpdate::on_update(&key, &old_value, &new_value);
+			},
+			(Some(old_value), None) => {
+				OnRemove::on_remove(&key, &old_value);
+			},
+			(None, Some(new_value)) => {
+				OnInsert::on_insert(&key, &new_value);
+			},
+			(None, None) => {},
+		}
+	}
+
+	/// Get the storage key used to fetch a value corresponding to a specific key.
+	pub fn hashed_key_for<KeyArg: EncodeLike<Key::Key>>(key: KeyArg) -> Vec<u8> {
+		<Map as storage::StorageNMap<Key, Value>>::hashed_key_for(key)
+	}
+
+	/// Load the value associated with the given key from the map.
+	pub fn get<KeyArg: EncodeLike<Key::Key>>(
+		key: KeyArg,
+	) -> <Map as storage::StorageNMap<Key, Value>>::Query {
+		<Map as storage::StorageNMap<Key, Value>>::get(key)
+	}
+
+	/// Try to get the value for the given key from the map.
+	///
+	/// Returns `Ok` if it exists, `Err` if not.
+	pub fn try_get<KeyArg: EncodeLike<Key::Key>>(key: KeyArg) -> Result<Value, ()> {
+		<Map as storage::StorageNMap<Key, Value>>::try_get(key)
+	}
+
+	/// Swap the values of two keys.
+	pub fn swap<KeyArg1: EncodeLike<Key::Key> + Clone, KeyArg2: EncodeLike<Key::Key> + Clone>(
+		key1: KeyArg1,
+		key2: KeyArg2,
+	) {
+		let maybe_value1 = Self::maybe_get(key1.clone());
+		let maybe_value2 = Self::maybe_get(key2.clone());
+		match (maybe_value1, maybe_value2) {
+			(Some(value1), Some(value2)) => {
+				// Both existed, and now swapped.
+				OnUpdate::on_update(&key1, &value1, &value2);
+				OnUpdate::on_update(&key2, &value2, &value1);
+			},
+			(Some(value1), None) => {
+				// val1 will be removed, val2 will be created.
+				OnRemove::on_remove(&key1, &value1);
+				OnInsert::on_insert(&key2, &value1);
+			},
+			(None, Some(value2)) => {
+				// val2 will be removed, val1 will be created.
+				OnRemove::on_remove(&key2, &value2);
+				OnInsert::on_insert(&key1, &value2);
+			},
+			(None, None) => {
+				// noop, no hook is fired.
+			},
+		}
+		<Map as storage::StorageNMap<Key, Value>>::swap(key1, key2)
+	}
+
+	/// Store a value to be associated with the given key from the map.
+	pub fn insert<KeyArg: EncodeLike<Key::Key>>(key: KeyArg, val: Value) {
+		OnInsert::on_insert(&key, &val);
+		<Map as storage::StorageNMap<Key, Value>>::insert(key, val)
+	}
+
+	/// Remove the value under a key.
+	pub fn remove<KeyArg: EncodeLike<Key::Key> + Clone>(key: KeyArg) {
+		if let Ok(removed) = Self::try_get(key.clone()) {
+			OnRemove::on_remove(&key, &removed);
+		}
+		<Map as storage::StorageNMap<Key, Value>>::remove(key)
+	}
+
+	/// Mutate the value under a key.
+	pub fn mutate<
+		KeyArg: EncodeLike<Key::Key> + Clone,
+		R,
+		F: FnOnce(&mut <Map as storage::StorageNMap<Key, Value>>::Query) -> R,
+	>(
+		key: KeyArg,
+		f: F,
+	) -> R {
+		let maybe_old_value = Self::maybe_get(key.clone());
+
+		let result = <Map as storage::StorageNMap<Key, Value>>::mutate(key.clone(), f);
+
+		let maybe_new_value = Self::maybe_get(key.clone());
+		Self::post_mutate_hooks(key, maybe_old_value, maybe_new_value);
+
+		result
+	}
+
+	/// Mutate the item, only if an `Ok` value is returned.
+	pub fn try_mutate<KeyArg, R, E, F>(key: KeyArg, f: F) -> Result<R, E>
+	where
+		KeyArg: EncodeLike<Key::Key> + Clone,
+		F: FnOnce(&mut <Map as storage::StorageNMap<Key, Value>>::Query) -> Result<R, E>,
+	{
+		let maybe_old_value = Self::maybe_get(key.clone());
+		let result = <Map as storage::StorageNMap<Key, Value>>::try_mutate(key.clone(), f);
+
+		if result.is_ok() {
+			let maybe_new_value = Self::maybe_get(key.clone());
+			Self::post_mutate_hooks(key, maybe_old_value, maybe_new_value);
+		}
+
+		result
+	}
+
+	/// Take the value under a key.
+	pub fn take<KeyArg: EncodeLike<Key::Key> + Clone>(
+		key: KeyArg,
+	) -> <Map as storage::StorageNMap<Key, Value>>::Query {
+		let maybe_old_value = Self::maybe_get(key.clone());
+		let r = <Map as storage::StorageNMap<Key, Value>>::take(key.clone());
+
+		if let Some(removed) = maybe_old_value {
+			OnRemove::on_remove(&key, &removed);
+		}
+
+		r
+	}
+}
@@ -0,0 +1,5 @@
+mod hooked_map;
+mod hooked_nmap;
+
+pub use hooked_map::{HookedMap, StorageOnInsert, StorageOnRemove, StorageOnUpdate};
+pub use hooked_nmap::HookedNMap;